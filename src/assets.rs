@@ -1,108 +1,305 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
 use bevy::asset::AssetLoader;
+use bevy::asset::AssetPath;
 use bevy::asset::AsyncReadExt;
 use bevy::asset::LoadContext;
+use bevy::asset::ReadAssetBytesError;
 use bevy::asset::io::Reader;
 use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
 use log::trace;
-use roxmltree::Document;
+use serde::Deserialize;
+use serde::Serialize;
 use thiserror::Error;
 
+use crate::internal::dom::ConvertContext;
+use crate::internal::dom::FontFamily;
 use crate::internal::dom::XFont;
 use crate::internal::dom::XNode;
+use crate::internal::include::component_definitions;
+use crate::internal::include::expand_includes;
+use crate::internal::include::read_partials;
+use crate::internal::markup::RawElement;
+use crate::internal::markup::RawNode;
+use crate::internal::markup::parse_document;
+use crate::internal::stylesheet::Stylesheet;
+use crate::internal::stylesheet::StylesheetError;
+use crate::internal::tailwind::SiblingPosition;
+use crate::internal::tailwind::StyleContext;
+use crate::internal::tailwind::UnsupportedClasses;
+use crate::internal::tailwind::VariantState;
+use crate::style::SpawnContext;
+use crate::theme::HtmlTheme;
 
 #[derive(Asset, TypePath, Debug)]
 pub struct HtmlTailwind {
-    pub dom: XNode,
-    pub fonts: HashMap<String, Handle<Font>>,
-    pub default_font: Option<Handle<Font>>,
+    /// Top-level nodes of the document
+    pub roots: Vec<XNode>,
+    /// Context for resolving the style of spawned nodes again
+    pub(crate) context: Arc<StyleContext>,
+}
+
+impl HtmlTailwind {
+    /// Spawn the root nodes as children of the entity
+    ///
+    /// Without a `Node` of its own, the entity becomes a container that fills its parent or the
+    /// whole window.
+    pub(crate) fn apply_to_entity(&self, commands: &mut EntityCommands, state: VariantState) {
+        commands.entry::<Node>().or_insert(Node {
+            width: percent(100),
+            height: percent(100),
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        });
+
+        let spawn = SpawnContext {
+            context: self.context.clone(),
+            state,
+        };
+        commands.with_children(|parent| {
+            for root in &self.roots {
+                let mut child_commands = parent.spawn_empty();
+                root.apply_to_entity(&mut child_commands, &spawn);
+            }
+        });
+    }
+}
+
+/// Settings for loading a single HTML file
+///
+/// They can be provided by `AssetServer::load_with_settings` or by a `.meta` file, fields
+/// missing in the file keep their default.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct HtmlUiLoaderSettings {
+    /// Treat unsupported classes, declarations and tags as errors instead of warnings
+    pub strict: bool,
+    /// Path of the font that is used if the document doesn't define a default font
+    pub default_font: Option<String>,
+    /// Allow more than one root node in the document
+    pub allow_multiple_roots: bool,
+    /// Maximum nesting depth of nodes, unlimited if not set
+    pub max_depth: Option<usize>,
+    /// Parser that is used for the document
+    pub parse_mode: ParseMode,
+    /// Treat unknown tags like `<div>` instead of skipping them
+    ///
+    /// In strict mode, unknown tags are always an error.
+    pub unknown_tags_as_div: bool,
+    /// Size of the root font in pixels, which is the size of `1rem`
+    ///
+    /// The spacing scale is based on it as well, e.g. `p-4` is `1rem`.
+    pub root_font_size: f32,
+}
+
+/// Selects how a document is parsed
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Choose the parser based on the file extension
+    ///
+    /// `.xml` files are parsed as XML, everything else as HTML
+    #[default]
+    Auto,
+    /// Strict XML
+    Xml,
+    /// Lenient HTML, allowing void elements like `<br>`, unquoted attributes and
+    /// character references like `&nbsp;`
+    Html,
+}
+
+impl Default for HtmlUiLoaderSettings {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            default_font: None,
+            allow_multiple_roots: true,
+            max_depth: None,
+            parse_mode: ParseMode::Auto,
+            unknown_tags_as_div: true,
+            root_font_size: 16.0,
+        }
+    }
 }
 
 #[derive(Default, TypePath)]
-pub struct HtmlUiAssetLoader;
+pub struct HtmlUiAssetLoader {
+    /// Theme of the plugin
+    pub(crate) theme: Arc<HtmlTheme>,
+    /// Path of a theme file that extends the theme of the plugin
+    pub(crate) theme_file: Option<String>,
+    /// Tags of the custom elements registered by the app
+    pub(crate) custom_elements: Arc<RwLock<HashSet<String>>>,
+}
 
 impl AssetLoader for HtmlUiAssetLoader {
     type Asset = HtmlTailwind;
-    type Settings = ();
+    type Settings = HtmlUiLoaderSettings;
     type Error = HtmlUiLoadError;
 
     async fn load(
         &self,
         reader: &mut dyn Reader,
-        _settings: &Self::Settings,
+        settings: &Self::Settings,
         load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut doc_raw = String::new();
         reader.read_to_string(&mut doc_raw).await?;
-        let document = Document::parse(&doc_raw)?;
 
-        let mut fonts = HashMap::new();
-        let mut default_font = None;
-        let mut body_node = None;
+        let path = load_context.path().clone_owned();
+        let nodes = parse_document(&doc_raw, &path, settings.parse_mode)?;
 
-        for child in document.root().children() {
-            if !child.is_element() {
-                continue;
-            }
+        // Partials and components are inlined before converting, as if they were part of the
+        // document
+        let components = component_definitions(&nodes, &path)?;
+        let partials = read_partials(&nodes, components, load_context, settings.parse_mode).await?;
+        let nodes = expand_includes(nodes, &partials, &mut vec![path.to_string()])?;
 
-            let tag_name = child.tag_name().name().to_lowercase();
-            match tag_name.as_str() {
-                "html" => {
-                    for child in child.children() {
-                        let tag_name = child.tag_name().name().to_lowercase();
-                        if !child.is_element() {
-                            continue;
-                        }
+        // Reading the theme file makes it a dependency, changing it reloads the document
+        let mut theme = self.theme.as_ref().clone();
+        if let Some(theme_file) = &self.theme_file {
+            let bytes = load_context.read_asset_bytes(theme_file).await?;
+            let file =
+                ron::de::from_bytes(&bytes).map_err(|error| HtmlUiLoadError::InvalidTheme {
+                    path: AssetPath::from(theme_file.clone()),
+                    error: Box::new(error),
+                })?;
+            theme.extend(file);
+        }
+
+        let custom_elements = self
+            .custom_elements
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let ctx = ConvertContext {
+            path: &path,
+            settings,
+            custom_elements: &custom_elements,
+        };
+
+        let unsupported = if settings.strict {
+            UnsupportedClasses::Error
+        } else {
+            UnsupportedClasses::Warn
+        };
 
-                        match tag_name.as_str() {
-                            "head" => {
-                                for head_child in child.children() {
-                                    let tag_name = head_child.tag_name().name().to_lowercase();
-                                    match tag_name.as_str() {
-                                        "font" => XFont::convert(
-                                            head_child,
-                                            load_context,
-                                            &mut fonts,
-                                            &mut default_font,
-                                        ),
-                                        _ => {}
+        let mut fonts = HashMap::new();
+        let mut stylesheet = Stylesheet::default();
+        let mut default_font = FontFamily::default();
+        let mut root_nodes = Vec::new();
+
+        // The html element is optional
+        let top_level =
+            nodes
+                .iter()
+                .filter_map(element)
+                .flat_map(|node| match node.name.as_str() {
+                    "html" => node.elements().collect(),
+                    _ => vec![node],
+                });
+
+        for child in top_level {
+            match child.name.as_str() {
+                "head" => {
+                    for head_child in child.elements() {
+                        match head_child.name.as_str() {
+                            "font" => XFont::convert(
+                                head_child,
+                                load_context,
+                                &mut fonts,
+                                &mut default_font,
+                            ),
+                            "style" => {
+                                let css = head_child.text();
+                                stylesheet.parse(&css, unsupported).map_err(|error| {
+                                    HtmlUiLoadError::InvalidStylesheet {
+                                        path: head_child.location.path(&path),
+                                        error,
                                     }
-                                }
+                                })?;
                             }
-                            "body" => {
-                                for body_child in child.children() {
-                                    let tag_name = body_child.tag_name().name().to_lowercase();
-                                    match tag_name.as_str() {
-                                        "div" | "button" | "span" | "p" => {
-                                            body_node = Some(XNode::convert(body_child));
-                                        }
-                                        _ => {}
-                                    }
-                                }
+                            "link" if head_child.attribute("rel") == Some("stylesheet") => {
+                                let Some(href) = head_child.attribute("href") else {
+                                    continue;
+                                };
+                                let href = AssetPath::from(href.to_string());
+                                // Reading the file makes it a dependency like the theme file
+                                let bytes = load_context.read_asset_bytes(href.clone()).await?;
+                                let css = String::from_utf8(bytes)?;
+                                stylesheet.parse(&css, unsupported).map_err(|error| {
+                                    HtmlUiLoadError::InvalidStylesheet { path: href, error }
+                                })?;
                             }
                             _ => {}
                         }
                     }
                 }
-                "div" | "button" | "span" | "p" => {
-                    body_node = Some(XNode::convert(child));
-                }
-                _ => {}
+                "body" => root_nodes.extend(child.elements()),
+                _ => root_nodes.push(child),
+            }
+        }
+
+        let mut roots = Vec::new();
+        for node in &root_nodes {
+            if let Some(node) = XNode::convert(node, &ctx, 0)? {
+                roots.push(node);
             }
         }
 
-        let mut body_node = body_node.expect("no body node");
-        body_node.resolve(load_context, &fonts, default_font.clone());
+        if !settings.allow_multiple_roots
+            && let Some(node) = roots.get(1)
+        {
+            let location = node.location();
+            return Err(HtmlUiLoadError::MultipleRootNodes {
+                path: location.path(&path),
+                line: location.line,
+                column: location.column,
+            });
+        }
+
+        if default_font.regular.is_none()
+            && let Some(font) = &settings.default_font
+        {
+            default_font.regular = Some(load_context.load(font.clone()));
+        }
 
-        let root = HtmlTailwind {
-            dom: body_node,
+        if roots.is_empty() {
+            return Err(HtmlUiLoadError::MissingRootNode { path });
+        }
+        let style_ctx = StyleContext {
             fonts,
             default_font,
+            unsupported,
+            rem: settings.root_font_size,
+            theme,
+            stylesheet,
+        };
+        let count = roots.len();
+        for (index, root) in roots.iter_mut().enumerate() {
+            root.resolve(
+                load_context,
+                &style_ctx,
+                None,
+                SiblingPosition { index, count },
+            )?;
+        }
+
+        // Unsupported classes were already reported while loading
+        let asset = HtmlTailwind {
+            roots,
+            context: Arc::new(StyleContext {
+                unsupported: UnsupportedClasses::Ignore,
+                ..style_ctx
+            }),
         };
 
-        trace!("Root node: {root:#?}");
+        trace!("Root nodes: {asset:#?}");
 
-        Ok(root)
+        Ok(asset)
     }
 
     fn extensions(&self) -> &[&str] {
@@ -110,12 +307,422 @@ impl AssetLoader for HtmlUiAssetLoader {
     }
 }
 
+fn element(node: &RawNode) -> Option<&RawElement> {
+    match node {
+        RawNode::Element(element) => Some(element),
+        RawNode::Text(_) => None,
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum HtmlUiLoadError {
     #[error("{0}")]
     Io(#[from] std::io::Error),
     #[error("UTF-8 Decode Error: {0}")]
     Utf8(#[from] std::string::FromUtf8Error),
-    #[error("XML parse Error: {0}")]
-    XMLError(#[from] roxmltree::Error),
+    #[error("{path}: XML parse Error: {error}")]
+    XMLError {
+        path: AssetPath<'static>,
+        error: Box<roxmltree::Error>,
+    },
+    #[error("{0}")]
+    ReadAssetBytes(#[from] ReadAssetBytesError),
+    #[error("{path}: Invalid theme: {error}")]
+    InvalidTheme {
+        path: AssetPath<'static>,
+        error: Box<ron::error::SpannedError>,
+    },
+    #[error("{path}: Invalid stylesheet: {error}")]
+    InvalidStylesheet {
+        path: AssetPath<'static>,
+        error: StylesheetError,
+    },
+    #[error("{path}: No root node found")]
+    MissingRootNode { path: AssetPath<'static> },
+    #[error("{path}:{line}:{column}: Multiple root nodes are not allowed")]
+    MultipleRootNodes {
+        path: AssetPath<'static>,
+        line: u32,
+        column: u32,
+    },
+    #[error("{path}:{line}:{column}: Maximum nesting depth of {max_depth} exceeded")]
+    MaxDepthExceeded {
+        path: AssetPath<'static>,
+        max_depth: usize,
+        line: u32,
+        column: u32,
+    },
+    #[error("{path}:{line}:{column}: Missing attribute {attribute} of <{tag}>")]
+    MissingAttribute {
+        path: AssetPath<'static>,
+        tag: &'static str,
+        attribute: &'static str,
+        line: u32,
+        column: u32,
+    },
+    #[error("{path}:{line}:{column}: The src of <include> can't contain props: {src}")]
+    IncludeWithProps {
+        path: AssetPath<'static>,
+        src: String,
+        line: u32,
+        column: u32,
+    },
+    #[error("{path}:{line}:{column}: Component {name} has the name of a built-in tag")]
+    ReservedComponentName {
+        path: AssetPath<'static>,
+        name: String,
+        line: u32,
+        column: u32,
+    },
+    #[error("{path}:{line}:{column}: Include cycle: {cycle}")]
+    IncludeCycle {
+        path: AssetPath<'static>,
+        cycle: String,
+        line: u32,
+        column: u32,
+    },
+    #[error("{path}:{line}:{column}: Unsupported tag: {tag}")]
+    UnsupportedTag {
+        path: AssetPath<'static>,
+        tag: String,
+        line: u32,
+        column: u32,
+    },
+    #[error("{path}:{line}:{column}: Block element <{tag}> inside of text")]
+    BlockInsideText {
+        path: AssetPath<'static>,
+        tag: String,
+        line: u32,
+        column: u32,
+    },
+    #[error("{path}:{line}:{column}: Unsupported style class: {class}")]
+    UnsupportedClass {
+        path: AssetPath<'static>,
+        class: String,
+        line: u32,
+        column: u32,
+    },
+    #[error("{path}:{line}:{column}: Unsupported style declaration: {declaration}")]
+    UnsupportedDeclaration {
+        path: AssetPath<'static>,
+        declaration: String,
+        line: u32,
+        column: u32,
+    },
+    #[error("{path}:{line}:{column}: No font registered with name {font}")]
+    UnknownFont {
+        path: AssetPath<'static>,
+        font: String,
+        line: u32,
+        column: u32,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::thread;
+    use std::time::Duration;
+
+    use bevy::asset::AssetLoadError;
+    use bevy::asset::LoadState;
+    use bevy::asset::io::AssetSourceBuilder;
+    use bevy::asset::io::AssetSourceId;
+    use bevy::asset::io::memory::Dir;
+    use bevy::asset::io::memory::MemoryAssetReader;
+
+    use super::*;
+
+    /// Load `doc.html` from the files with the default loader
+    fn load(
+        files: &[(&str, &str)],
+        settings: HtmlUiLoaderSettings,
+    ) -> Result<HtmlTailwind, Arc<AssetLoadError>> {
+        load_with(HtmlUiAssetLoader::default(), files, settings)
+    }
+
+    /// Load `doc.html` from the files with the loader
+    fn load_with(
+        loader: HtmlUiAssetLoader,
+        files: &[(&str, &str)],
+        settings: HtmlUiLoaderSettings,
+    ) -> Result<HtmlTailwind, Arc<AssetLoadError>> {
+        let dir = Dir::default();
+        for (path, text) in files {
+            dir.insert_asset_text(Path::new(path), text);
+        }
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin {
+                watch_for_changes_override: Some(false),
+                ..Default::default()
+            },
+        ))
+        .init_asset::<HtmlTailwind>()
+        .register_asset_loader(loader);
+
+        let handle = app
+            .world()
+            .resource::<AssetServer>()
+            .load_with_settings("doc.html", move |s: &mut HtmlUiLoaderSettings| {
+                *s = settings.clone()
+            });
+        for _ in 0..10_000 {
+            app.update();
+            match app.world().resource::<AssetServer>().load_state(&handle) {
+                LoadState::Loaded => {
+                    let mut assets = app.world_mut().resource_mut::<Assets<HtmlTailwind>>();
+                    return Ok(assets.remove(&handle).unwrap());
+                }
+                LoadState::Failed(error) => return Err(error),
+                _ => thread::sleep(Duration::from_millis(1)),
+            }
+        }
+        panic!("Loading doc.html didn't finish");
+    }
+
+    /// Error of the loader a load failed with
+    fn loader_error(error: &AssetLoadError) -> &HtmlUiLoadError {
+        match error {
+            AssetLoadError::AssetLoaderError(error) => error.error().downcast_ref().unwrap(),
+            error => panic!("Unexpected error: {error}"),
+        }
+    }
+
+    fn load_doc(
+        doc: &str,
+        settings: HtmlUiLoaderSettings,
+    ) -> Result<HtmlTailwind, Arc<AssetLoadError>> {
+        load(&[("doc.html", doc)], settings)
+    }
+
+    #[test]
+    fn missing_settings_keep_their_default() {
+        let settings = ron::from_str::<HtmlUiLoaderSettings>("(strict: true)").unwrap();
+        assert!(settings.strict);
+        assert!(settings.allow_multiple_roots);
+        assert!(settings.unknown_tags_as_div);
+        assert_eq!(settings.parse_mode, ParseMode::Auto);
+        assert_eq!(settings.root_font_size, 16.0);
+    }
+
+    #[test]
+    fn lenient_mode_skips_unsupported_content() {
+        let doc = "<div class=\"p-4 unknown\" style=\"float: left\"><blink>a</blink></div>";
+        let asset = load_doc(doc, HtmlUiLoaderSettings::default()).unwrap();
+        assert_eq!(asset.roots.len(), 1);
+    }
+
+    #[test]
+    fn strict_mode_reports_unsupported_content() {
+        let strict = HtmlUiLoaderSettings {
+            strict: true,
+            ..Default::default()
+        };
+        let error =
+            load_doc("<div>\n  <div class=\"unknown\"/></div>", strict.clone()).unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::UnsupportedClass { class, line: 2, column: 3, .. } if class == "unknown"
+        ));
+
+        let error = load_doc("<div style=\"float: left\"/>", strict.clone()).unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::UnsupportedDeclaration { .. }
+        ));
+
+        let error = load_doc("<div><blink/></div>", strict).unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::UnsupportedTag { tag, .. } if tag == "blink"
+        ));
+    }
+
+    #[test]
+    fn errors_inside_partials_report_the_partial() {
+        let strict = HtmlUiLoaderSettings {
+            strict: true,
+            ..Default::default()
+        };
+        let files = |row: &'static str| {
+            [
+                ("doc.html", "<div>\n<include src=\"parts/row.html\"/></div>"),
+                ("parts/row.html", row),
+            ]
+        };
+
+        let error = load(
+            &files("<p>\n  <span class=\"unknown\">a</span></p>"),
+            strict.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::UnsupportedClass { path, line: 2, column: 3, .. }
+                if path.to_string() == "parts/row.html"
+        ));
+
+        let error = load(&files("<div><blink/></div>"), strict.clone()).unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::UnsupportedTag { path, line: 1, column: 6, .. }
+                if path.to_string() == "parts/row.html"
+        ));
+
+        let settings = HtmlUiLoaderSettings {
+            max_depth: Some(2),
+            ..Default::default()
+        };
+        let error = load(&files("<div><div/></div>"), settings).unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::MaxDepthExceeded { path, line: 1, column: 6, .. }
+                if path.to_string() == "parts/row.html"
+        ));
+
+        // Elements of the document keep its path
+        let error = load_doc("<div class=\"unknown\"/>", strict).unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::UnsupportedClass { path, .. } if path.to_string() == "doc.html"
+        ));
+    }
+
+    #[test]
+    fn errors_inside_components_report_the_template() {
+        let strict = HtmlUiLoaderSettings {
+            strict: true,
+            ..Default::default()
+        };
+        let doc = "<head><component name=\"card\" src=\"ui/card.html\"/></head>\n\
+                   <card title=\"A\"><p class=\"also-unknown\">a</p></card>";
+        let card = "<div>\n  <p class=\"{{ title }} unknown\">{{ title }}</p><slot/></div>";
+
+        let error = load(&[("doc.html", doc), ("ui/card.html", card)], strict.clone()).unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::UnsupportedClass { path, class, line: 2, column: 3 }
+                if path.to_string() == "ui/card.html" && class == "A"
+        ));
+
+        // The children of the component belong to the document
+        let card = "<div><slot/></div>";
+        let error = load(&[("doc.html", doc), ("ui/card.html", card)], strict).unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::UnsupportedClass { path, line: 2, .. }
+                if path.to_string() == "doc.html"
+        ));
+    }
+
+    #[test]
+    fn max_depth() {
+        let doc = "<div>\n<div>\n<div/></div></div>";
+        let settings = |max_depth| HtmlUiLoaderSettings {
+            max_depth: Some(max_depth),
+            ..Default::default()
+        };
+        assert!(load_doc(doc, settings(3)).is_ok());
+        let error = load_doc(doc, settings(2)).unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::MaxDepthExceeded {
+                max_depth: 2,
+                line: 3,
+                column: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn multiple_roots() {
+        let doc = "<div/>\n<p>a</p>";
+        let asset = load_doc(doc, HtmlUiLoaderSettings::default()).unwrap();
+        assert_eq!(asset.roots.len(), 2);
+
+        let single = HtmlUiLoaderSettings {
+            allow_multiple_roots: false,
+            ..Default::default()
+        };
+        let error = load_doc(doc, single).unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::MultipleRootNodes {
+                line: 2,
+                column: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn missing_root() {
+        let error = load_doc("<head></head>", HtmlUiLoaderSettings::default()).unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::MissingRootNode { .. }
+        ));
+    }
+
+    #[test]
+    fn unknown_tags() {
+        let doc = "<blink>a</blink><div/>";
+        let asset = load_doc(doc, HtmlUiLoaderSettings::default()).unwrap();
+        assert_eq!(asset.roots.len(), 2);
+
+        let skip = HtmlUiLoaderSettings {
+            unknown_tags_as_div: false,
+            ..Default::default()
+        };
+        let asset = load_doc(doc, skip).unwrap();
+        assert_eq!(asset.roots.len(), 1);
+    }
+
+    #[test]
+    fn parse_mode() {
+        let doc = "<div>a<br>b</div>";
+        assert!(load_doc(doc, HtmlUiLoaderSettings::default()).is_ok());
+
+        let xml = HtmlUiLoaderSettings {
+            parse_mode: ParseMode::Xml,
+            ..Default::default()
+        };
+        let error = load_doc(doc, xml).unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::XMLError { path, .. } if *path == AssetPath::from("doc.html")
+        ));
+    }
+
+    #[test]
+    fn theme_file() {
+        let strict = HtmlUiLoaderSettings {
+            strict: true,
+            ..Default::default()
+        };
+        let loader = || HtmlUiAssetLoader {
+            theme_file: Some("theme.ron".to_string()),
+            ..Default::default()
+        };
+        let doc = ("doc.html", "<div class=\"bg-brand\"/>");
+
+        let theme = ("theme.ron", r##"(colors: { "brand": "#ff8800" })"##);
+        assert!(load_with(loader(), &[doc, theme], strict.clone()).is_ok());
+        assert!(load(&[doc], strict.clone()).is_err());
+
+        let theme = ("theme.ron", r##"(colors: { "brand": #ff8800 })"##);
+        let error = load_with(loader(), &[doc, theme], strict).unwrap_err();
+        assert!(matches!(
+            loader_error(&error),
+            HtmlUiLoadError::InvalidTheme { path, .. } if *path == AssetPath::from("theme.ron")
+        ));
+    }
 }
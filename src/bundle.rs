@@ -17,6 +17,35 @@ impl From<Handle<HtmlTailwind>> for HtmlTailwindHandle {
 #[derive(Component, Reflect, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct HtmlId(pub String);
 
+/// Component that stores all attributes of a node in the order of the document
+///
+/// Nodes without attributes, like runs of text, don't have it.
+#[derive(Component, Reflect, Debug, Clone, Default, PartialEq, Eq)]
+pub struct HtmlAttributes(pub Vec<(String, String)>);
+
+impl HtmlAttributes {
+    /// Value of the attribute with the given name
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Names and values of the attributes
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+/// Component of a custom element that is built by the function registered for its tag
+#[derive(Component, Debug, Clone)]
+pub(crate) struct HtmlCustomElement {
+    pub tag: String,
+}
+
 /// Marker component that is attached when the node tree is spawned on the entity
 #[derive(Component)]
 pub struct HtmlTailwindSpawned;
@@ -40,3 +69,175 @@ impl Default for HtmlTailwindBundle {
         }
     }
 }
+
+/// Classes of a node spawned from a document
+///
+/// Changing them resolves the style of the node and its descendants again. Only the
+/// components that change are replaced, e.g. toggling `hidden` only replaces the `Node`.
+/// With [`HtmlClassesExt`] in scope, `add`, `remove` and `set` don't mark the component as
+/// changed if the classes stay the same.
+///
+/// ```ignore
+/// fn toggle_menu(mut menus: Query<&mut HtmlClasses, With<Menu>>) {
+///     for mut classes in &mut menus {
+///         classes.toggle("hidden");
+///     }
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Default, PartialEq, Eq)]
+pub struct HtmlClasses(String);
+
+impl HtmlClasses {
+    pub fn new(classes: impl Into<String>) -> Self {
+        Self(classes.into())
+    }
+
+    /// All classes separated by spaces
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.split_ascii_whitespace()
+    }
+
+    pub fn contains(&self, class: &str) -> bool {
+        self.iter().any(|existing| existing == class)
+    }
+
+    /// Add a class after the existing classes, unless it is already present
+    pub fn add(&mut self, class: &str) {
+        if self.contains(class) {
+            return;
+        }
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        self.0.push_str(class);
+    }
+
+    pub fn remove(&mut self, class: &str) {
+        if !self.contains(class) {
+            return;
+        }
+        self.0 = self
+            .iter()
+            .filter(|existing| *existing != class)
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+
+    /// Add the class if it is missing and remove it otherwise
+    ///
+    /// Returns whether the class is present afterwards.
+    pub fn toggle(&mut self, class: &str) -> bool {
+        if self.contains(class) {
+            self.remove(class);
+            false
+        } else {
+            self.add(class);
+            true
+        }
+    }
+
+    /// Replace all classes
+    pub fn set(&mut self, classes: impl Into<String>) {
+        self.0 = classes.into();
+    }
+}
+
+/// Extension trait for changing [`HtmlClasses`] in queries, which only marks them as changed if
+/// the classes differ afterwards
+///
+/// Without it, the methods of [`HtmlClasses`] are called through `DerefMut`, which marks the
+/// component as changed and restyles the node even if it already had the class.
+pub trait HtmlClassesExt {
+    /// Add a class after the existing classes, unless it is already present
+    fn add(&mut self, class: &str);
+
+    fn remove(&mut self, class: &str);
+
+    /// Replace all classes
+    fn set(&mut self, classes: impl Into<String>);
+}
+
+impl HtmlClassesExt for Mut<'_, HtmlClasses> {
+    fn add(&mut self, class: &str) {
+        if !self.contains(class) {
+            HtmlClasses::add(self, class);
+        }
+    }
+
+    fn remove(&mut self, class: &str) {
+        if self.contains(class) {
+            HtmlClasses::remove(self, class);
+        }
+    }
+
+    fn set(&mut self, classes: impl Into<String>) {
+        let classes = classes.into();
+        if self.0 != classes {
+            HtmlClasses::set(self, classes);
+        }
+    }
+}
+
+impl std::fmt::Display for HtmlClasses {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn change_classes() {
+        let mut classes = HtmlClasses::new("p-4  flex");
+        classes.add("hidden");
+        classes.add("flex");
+        assert_eq!(classes.as_str(), "p-4  flex hidden");
+        assert!(classes.contains("hidden"));
+        assert!(!classes.contains("p"));
+
+        classes.remove("p-4");
+        classes.remove("missing");
+        assert_eq!(classes.as_str(), "flex hidden");
+
+        assert!(!classes.toggle("hidden"));
+        assert_eq!(classes.as_str(), "flex");
+        assert!(classes.toggle("hidden"));
+        assert_eq!(classes.as_str(), "flex hidden");
+
+        classes.set("grid");
+        assert_eq!(classes.iter().collect::<Vec<_>>(), ["grid"]);
+
+        let mut empty = HtmlClasses::default();
+        empty.add("flex");
+        assert_eq!(empty.as_str(), "flex");
+    }
+
+    #[test]
+    fn unchanged_classes_are_not_marked_as_changed() {
+        let mut world = World::new();
+        let entity = world.spawn(HtmlClasses::new("flex hidden")).id();
+
+        let changed = |world: &mut World, change: fn(&mut Mut<HtmlClasses>)| {
+            world.clear_trackers();
+            let mut classes = world.get_mut::<HtmlClasses>(entity).unwrap();
+            change(&mut classes);
+            classes.is_changed()
+        };
+
+        assert!(!changed(&mut world, |classes| classes.add("flex")));
+        assert!(!changed(&mut world, |classes| classes.remove("p-4")));
+        assert!(!changed(&mut world, |classes| classes.set("flex hidden")));
+        assert!(changed(&mut world, |classes| classes.add("p-4")));
+        assert!(changed(&mut world, |classes| classes.remove("p-4")));
+        assert!(changed(&mut world, |classes| classes.set("grid")));
+        assert!(changed(&mut world, |classes| {
+            classes.toggle("hidden");
+        }));
+    }
+}
@@ -0,0 +1,672 @@
+//! Colors of the Tailwind palette and CSS color values
+
+use bevy::color::palettes::css;
+use bevy::color::palettes::tailwind;
+use bevy::prelude::*;
+
+/// Shades of every color of the Tailwind palette, from light to dark
+const SHADES: [&str; 11] = [
+    "50", "100", "200", "300", "400", "500", "600", "700", "800", "900", "950",
+];
+
+/// Color of the Tailwind palette, e.g. `slate` and `800`
+pub fn tailwind_color(name: &str, shade: &str) -> Option<Srgba> {
+    let shades = match name {
+        "amber" => [
+            tailwind::AMBER_50,
+            tailwind::AMBER_100,
+            tailwind::AMBER_200,
+            tailwind::AMBER_300,
+            tailwind::AMBER_400,
+            tailwind::AMBER_500,
+            tailwind::AMBER_600,
+            tailwind::AMBER_700,
+            tailwind::AMBER_800,
+            tailwind::AMBER_900,
+            tailwind::AMBER_950,
+        ],
+        "blue" => [
+            tailwind::BLUE_50,
+            tailwind::BLUE_100,
+            tailwind::BLUE_200,
+            tailwind::BLUE_300,
+            tailwind::BLUE_400,
+            tailwind::BLUE_500,
+            tailwind::BLUE_600,
+            tailwind::BLUE_700,
+            tailwind::BLUE_800,
+            tailwind::BLUE_900,
+            tailwind::BLUE_950,
+        ],
+        "cyan" => [
+            tailwind::CYAN_50,
+            tailwind::CYAN_100,
+            tailwind::CYAN_200,
+            tailwind::CYAN_300,
+            tailwind::CYAN_400,
+            tailwind::CYAN_500,
+            tailwind::CYAN_600,
+            tailwind::CYAN_700,
+            tailwind::CYAN_800,
+            tailwind::CYAN_900,
+            tailwind::CYAN_950,
+        ],
+        "emerald" => [
+            tailwind::EMERALD_50,
+            tailwind::EMERALD_100,
+            tailwind::EMERALD_200,
+            tailwind::EMERALD_300,
+            tailwind::EMERALD_400,
+            tailwind::EMERALD_500,
+            tailwind::EMERALD_600,
+            tailwind::EMERALD_700,
+            tailwind::EMERALD_800,
+            tailwind::EMERALD_900,
+            tailwind::EMERALD_950,
+        ],
+        "fuchsia" => [
+            tailwind::FUCHSIA_50,
+            tailwind::FUCHSIA_100,
+            tailwind::FUCHSIA_200,
+            tailwind::FUCHSIA_300,
+            tailwind::FUCHSIA_400,
+            tailwind::FUCHSIA_500,
+            tailwind::FUCHSIA_600,
+            tailwind::FUCHSIA_700,
+            tailwind::FUCHSIA_800,
+            tailwind::FUCHSIA_900,
+            tailwind::FUCHSIA_950,
+        ],
+        "gray" => [
+            tailwind::GRAY_50,
+            tailwind::GRAY_100,
+            tailwind::GRAY_200,
+            tailwind::GRAY_300,
+            tailwind::GRAY_400,
+            tailwind::GRAY_500,
+            tailwind::GRAY_600,
+            tailwind::GRAY_700,
+            tailwind::GRAY_800,
+            tailwind::GRAY_900,
+            tailwind::GRAY_950,
+        ],
+        "green" => [
+            tailwind::GREEN_50,
+            tailwind::GREEN_100,
+            tailwind::GREEN_200,
+            tailwind::GREEN_300,
+            tailwind::GREEN_400,
+            tailwind::GREEN_500,
+            tailwind::GREEN_600,
+            tailwind::GREEN_700,
+            tailwind::GREEN_800,
+            tailwind::GREEN_900,
+            tailwind::GREEN_950,
+        ],
+        "indigo" => [
+            tailwind::INDIGO_50,
+            tailwind::INDIGO_100,
+            tailwind::INDIGO_200,
+            tailwind::INDIGO_300,
+            tailwind::INDIGO_400,
+            tailwind::INDIGO_500,
+            tailwind::INDIGO_600,
+            tailwind::INDIGO_700,
+            tailwind::INDIGO_800,
+            tailwind::INDIGO_900,
+            tailwind::INDIGO_950,
+        ],
+        "lime" => [
+            tailwind::LIME_50,
+            tailwind::LIME_100,
+            tailwind::LIME_200,
+            tailwind::LIME_300,
+            tailwind::LIME_400,
+            tailwind::LIME_500,
+            tailwind::LIME_600,
+            tailwind::LIME_700,
+            tailwind::LIME_800,
+            tailwind::LIME_900,
+            tailwind::LIME_950,
+        ],
+        "neutral" => [
+            tailwind::NEUTRAL_50,
+            tailwind::NEUTRAL_100,
+            tailwind::NEUTRAL_200,
+            tailwind::NEUTRAL_300,
+            tailwind::NEUTRAL_400,
+            tailwind::NEUTRAL_500,
+            tailwind::NEUTRAL_600,
+            tailwind::NEUTRAL_700,
+            tailwind::NEUTRAL_800,
+            tailwind::NEUTRAL_900,
+            tailwind::NEUTRAL_950,
+        ],
+        "orange" => [
+            tailwind::ORANGE_50,
+            tailwind::ORANGE_100,
+            tailwind::ORANGE_200,
+            tailwind::ORANGE_300,
+            tailwind::ORANGE_400,
+            tailwind::ORANGE_500,
+            tailwind::ORANGE_600,
+            tailwind::ORANGE_700,
+            tailwind::ORANGE_800,
+            tailwind::ORANGE_900,
+            tailwind::ORANGE_950,
+        ],
+        "pink" => [
+            tailwind::PINK_50,
+            tailwind::PINK_100,
+            tailwind::PINK_200,
+            tailwind::PINK_300,
+            tailwind::PINK_400,
+            tailwind::PINK_500,
+            tailwind::PINK_600,
+            tailwind::PINK_700,
+            tailwind::PINK_800,
+            tailwind::PINK_900,
+            tailwind::PINK_950,
+        ],
+        "purple" => [
+            tailwind::PURPLE_50,
+            tailwind::PURPLE_100,
+            tailwind::PURPLE_200,
+            tailwind::PURPLE_300,
+            tailwind::PURPLE_400,
+            tailwind::PURPLE_500,
+            tailwind::PURPLE_600,
+            tailwind::PURPLE_700,
+            tailwind::PURPLE_800,
+            tailwind::PURPLE_900,
+            tailwind::PURPLE_950,
+        ],
+        "red" => [
+            tailwind::RED_50,
+            tailwind::RED_100,
+            tailwind::RED_200,
+            tailwind::RED_300,
+            tailwind::RED_400,
+            tailwind::RED_500,
+            tailwind::RED_600,
+            tailwind::RED_700,
+            tailwind::RED_800,
+            tailwind::RED_900,
+            tailwind::RED_950,
+        ],
+        "rose" => [
+            tailwind::ROSE_50,
+            tailwind::ROSE_100,
+            tailwind::ROSE_200,
+            tailwind::ROSE_300,
+            tailwind::ROSE_400,
+            tailwind::ROSE_500,
+            tailwind::ROSE_600,
+            tailwind::ROSE_700,
+            tailwind::ROSE_800,
+            tailwind::ROSE_900,
+            tailwind::ROSE_950,
+        ],
+        "sky" => [
+            tailwind::SKY_50,
+            tailwind::SKY_100,
+            tailwind::SKY_200,
+            tailwind::SKY_300,
+            tailwind::SKY_400,
+            tailwind::SKY_500,
+            tailwind::SKY_600,
+            tailwind::SKY_700,
+            tailwind::SKY_800,
+            tailwind::SKY_900,
+            tailwind::SKY_950,
+        ],
+        "slate" => [
+            tailwind::SLATE_50,
+            tailwind::SLATE_100,
+            tailwind::SLATE_200,
+            tailwind::SLATE_300,
+            tailwind::SLATE_400,
+            tailwind::SLATE_500,
+            tailwind::SLATE_600,
+            tailwind::SLATE_700,
+            tailwind::SLATE_800,
+            tailwind::SLATE_900,
+            tailwind::SLATE_950,
+        ],
+        "stone" => [
+            tailwind::STONE_50,
+            tailwind::STONE_100,
+            tailwind::STONE_200,
+            tailwind::STONE_300,
+            tailwind::STONE_400,
+            tailwind::STONE_500,
+            tailwind::STONE_600,
+            tailwind::STONE_700,
+            tailwind::STONE_800,
+            tailwind::STONE_900,
+            tailwind::STONE_950,
+        ],
+        "teal" => [
+            tailwind::TEAL_50,
+            tailwind::TEAL_100,
+            tailwind::TEAL_200,
+            tailwind::TEAL_300,
+            tailwind::TEAL_400,
+            tailwind::TEAL_500,
+            tailwind::TEAL_600,
+            tailwind::TEAL_700,
+            tailwind::TEAL_800,
+            tailwind::TEAL_900,
+            tailwind::TEAL_950,
+        ],
+        "violet" => [
+            tailwind::VIOLET_50,
+            tailwind::VIOLET_100,
+            tailwind::VIOLET_200,
+            tailwind::VIOLET_300,
+            tailwind::VIOLET_400,
+            tailwind::VIOLET_500,
+            tailwind::VIOLET_600,
+            tailwind::VIOLET_700,
+            tailwind::VIOLET_800,
+            tailwind::VIOLET_900,
+            tailwind::VIOLET_950,
+        ],
+        "yellow" => [
+            tailwind::YELLOW_50,
+            tailwind::YELLOW_100,
+            tailwind::YELLOW_200,
+            tailwind::YELLOW_300,
+            tailwind::YELLOW_400,
+            tailwind::YELLOW_500,
+            tailwind::YELLOW_600,
+            tailwind::YELLOW_700,
+            tailwind::YELLOW_800,
+            tailwind::YELLOW_900,
+            tailwind::YELLOW_950,
+        ],
+        "zinc" => [
+            tailwind::ZINC_50,
+            tailwind::ZINC_100,
+            tailwind::ZINC_200,
+            tailwind::ZINC_300,
+            tailwind::ZINC_400,
+            tailwind::ZINC_500,
+            tailwind::ZINC_600,
+            tailwind::ZINC_700,
+            tailwind::ZINC_800,
+            tailwind::ZINC_900,
+            tailwind::ZINC_950,
+        ],
+        _ => return None,
+    };
+    let index = SHADES.iter().position(|s| *s == shade)?;
+
+    Some(shades[index])
+}
+
+/// Named CSS color, e.g. `rebeccapurple`
+pub fn css_color(name: &str) -> Option<Srgba> {
+    Some(match name {
+        "aliceblue" => css::ALICE_BLUE,
+        "antiquewhite" => css::ANTIQUE_WHITE,
+        "aqua" => css::AQUA,
+        "aquamarine" => css::AQUAMARINE,
+        "azure" => css::AZURE,
+        "beige" => css::BEIGE,
+        "bisque" => css::BISQUE,
+        "black" => css::BLACK,
+        "blanchedalmond" => css::BLANCHED_ALMOND,
+        "blue" => css::BLUE,
+        "blueviolet" => css::BLUE_VIOLET,
+        "brown" => css::BROWN,
+        "burlywood" => css::BURLYWOOD,
+        "cadetblue" => css::CADET_BLUE,
+        "chartreuse" => css::CHARTREUSE,
+        "chocolate" => css::CHOCOLATE,
+        "coral" => css::CORAL,
+        "cornflowerblue" => css::CORNFLOWER_BLUE,
+        "cornsilk" => css::CORNSILK,
+        "crimson" => css::CRIMSON,
+        "cyan" => css::AQUA,
+        "darkblue" => css::DARK_BLUE,
+        "darkcyan" => css::DARK_CYAN,
+        "darkgoldenrod" => css::DARK_GOLDENROD,
+        "darkgray" => css::DARK_GRAY,
+        "darkgreen" => css::DARK_GREEN,
+        "darkgrey" => css::DARK_GREY,
+        "darkkhaki" => css::DARK_KHAKI,
+        "darkmagenta" => css::DARK_MAGENTA,
+        "darkolivegreen" => css::DARK_OLIVEGREEN,
+        "darkorange" => css::DARK_ORANGE,
+        "darkorchid" => css::DARK_ORCHID,
+        "darkred" => css::DARK_RED,
+        "darksalmon" => css::DARK_SALMON,
+        "darkseagreen" => css::DARK_SEA_GREEN,
+        "darkslateblue" => css::DARK_SLATE_BLUE,
+        "darkslategray" => css::DARK_SLATE_GRAY,
+        "darkslategrey" => css::DARK_SLATE_GREY,
+        "darkturquoise" => css::DARK_TURQUOISE,
+        "darkviolet" => css::DARK_VIOLET,
+        "deeppink" => css::DEEP_PINK,
+        "deepskyblue" => css::DEEP_SKY_BLUE,
+        "dimgray" => css::DIM_GRAY,
+        "dimgrey" => css::DIM_GREY,
+        "dodgerblue" => css::DODGER_BLUE,
+        "firebrick" => css::FIRE_BRICK,
+        "floralwhite" => css::FLORAL_WHITE,
+        "forestgreen" => css::FOREST_GREEN,
+        "fuchsia" => css::FUCHSIA,
+        "gainsboro" => css::GAINSBORO,
+        "ghostwhite" => css::GHOST_WHITE,
+        "gold" => css::GOLD,
+        "goldenrod" => css::GOLDENROD,
+        "gray" => css::GRAY,
+        "green" => css::GREEN,
+        "greenyellow" => css::GREEN_YELLOW,
+        "grey" => css::GREY,
+        "honeydew" => css::HONEYDEW,
+        "hotpink" => css::HOT_PINK,
+        "indianred" => css::INDIAN_RED,
+        "indigo" => css::INDIGO,
+        "ivory" => css::IVORY,
+        "khaki" => css::KHAKI,
+        "lavender" => css::LAVENDER,
+        "lavenderblush" => css::LAVENDER_BLUSH,
+        "lawngreen" => css::LAWN_GREEN,
+        "lemonchiffon" => css::LEMON_CHIFFON,
+        "lightblue" => css::LIGHT_BLUE,
+        "lightcoral" => css::LIGHT_CORAL,
+        "lightcyan" => css::LIGHT_CYAN,
+        "lightgoldenrodyellow" => css::LIGHT_GOLDENROD_YELLOW,
+        "lightgray" => css::LIGHT_GRAY,
+        "lightgreen" => css::LIGHT_GREEN,
+        "lightgrey" => css::LIGHT_GREY,
+        "lightpink" => css::LIGHT_PINK,
+        "lightsalmon" => css::LIGHT_SALMON,
+        "lightseagreen" => css::LIGHT_SEA_GREEN,
+        "lightskyblue" => css::LIGHT_SKY_BLUE,
+        "lightslategray" => css::LIGHT_SLATE_GRAY,
+        "lightslategrey" => css::LIGHT_SLATE_GREY,
+        "lightsteelblue" => css::LIGHT_STEEL_BLUE,
+        "lightyellow" => css::LIGHT_YELLOW,
+        "lime" => css::LIME,
+        "limegreen" => css::LIMEGREEN,
+        "linen" => css::LINEN,
+        "magenta" => css::MAGENTA,
+        "maroon" => css::MAROON,
+        "mediumaquamarine" => css::MEDIUM_AQUAMARINE,
+        "mediumblue" => css::MEDIUM_BLUE,
+        "mediumorchid" => css::MEDIUM_ORCHID,
+        "mediumpurple" => css::MEDIUM_PURPLE,
+        "mediumseagreen" => css::MEDIUM_SEA_GREEN,
+        "mediumslateblue" => css::MEDIUM_SLATE_BLUE,
+        "mediumspringgreen" => css::MEDIUM_SPRING_GREEN,
+        "mediumturquoise" => css::MEDIUM_TURQUOISE,
+        "mediumvioletred" => css::MEDIUM_VIOLET_RED,
+        "midnightblue" => css::MIDNIGHT_BLUE,
+        "mintcream" => css::MINT_CREAM,
+        "mistyrose" => css::MISTY_ROSE,
+        "moccasin" => css::MOCCASIN,
+        "navajowhite" => css::NAVAJO_WHITE,
+        "navy" => css::NAVY,
+        "oldlace" => css::OLD_LACE,
+        "olive" => css::OLIVE,
+        "olivedrab" => css::OLIVE_DRAB,
+        "orange" => css::ORANGE,
+        "orangered" => css::ORANGE_RED,
+        "orchid" => css::ORCHID,
+        "palegoldenrod" => css::PALE_GOLDENROD,
+        "palegreen" => css::PALE_GREEN,
+        "paleturquoise" => css::PALE_TURQUOISE,
+        "palevioletred" => css::PALE_VIOLETRED,
+        "papayawhip" => css::PAPAYA_WHIP,
+        "peachpuff" => css::PEACHPUFF,
+        "peru" => css::PERU,
+        "pink" => css::PINK,
+        "plum" => css::PLUM,
+        "powderblue" => css::POWDER_BLUE,
+        "purple" => css::PURPLE,
+        "rebeccapurple" => css::REBECCA_PURPLE,
+        "red" => css::RED,
+        "rosybrown" => css::ROSY_BROWN,
+        "royalblue" => css::ROYAL_BLUE,
+        "saddlebrown" => css::SADDLE_BROWN,
+        "salmon" => css::SALMON,
+        "sandybrown" => css::SANDY_BROWN,
+        "seagreen" => css::SEA_GREEN,
+        "seashell" => css::SEASHELL,
+        "sienna" => css::SIENNA,
+        "silver" => css::SILVER,
+        "skyblue" => css::SKY_BLUE,
+        "slateblue" => css::SLATE_BLUE,
+        "slategray" => css::SLATE_GRAY,
+        "slategrey" => css::SLATE_GREY,
+        "snow" => css::SNOW,
+        "springgreen" => css::SPRING_GREEN,
+        "steelblue" => css::STEEL_BLUE,
+        "tan" => css::TAN,
+        "teal" => css::TEAL,
+        "thistle" => css::THISTLE,
+        "tomato" => css::TOMATO,
+        "turquoise" => css::TURQUOISE,
+        "violet" => css::VIOLET,
+        "wheat" => css::WHEAT,
+        "white" => css::WHITE,
+        "whitesmoke" => css::WHITE_SMOKE,
+        "yellow" => css::YELLOW,
+        "yellowgreen" => css::YELLOW_GREEN,
+        _ => return None,
+    })
+}
+
+/// Parse a CSS color value
+///
+/// Supported are hex colors (`#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`), the functions `rgb()`,
+/// `rgba()`, `hsl()`, `hsla()` and `oklch()` with comma or space separated arguments,
+/// named colors and `transparent`.
+pub fn parse_css_color(value: &str) -> Option<Color> {
+    let value = value.trim().to_ascii_lowercase();
+
+    if value.starts_with('#') {
+        return Srgba::hex(&value).ok().map(Color::from);
+    }
+    if value == "transparent" {
+        return Some(Color::NONE);
+    }
+    if let Some(color) = css_color(&value) {
+        return Some(color.into());
+    }
+
+    let (function, args) = value.strip_suffix(')')?.split_once('(')?;
+    let args = args
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|arg| !arg.is_empty())
+        .collect::<Vec<_>>();
+    let (channels, alpha) = match args.as_slice() {
+        [a, b, c] => ([*a, *b, *c], 1.0),
+        [a, b, c, alpha] => ([*a, *b, *c], parse_fraction(alpha, 1.0)?),
+        _ => return None,
+    };
+    let [a, b, c] = channels;
+
+    match function.trim() {
+        "rgb" | "rgba" => Some(Color::srgba(
+            parse_fraction(a, 255.0)?,
+            parse_fraction(b, 255.0)?,
+            parse_fraction(c, 255.0)?,
+            alpha,
+        )),
+        "hsl" | "hsla" => Some(Color::hsla(
+            parse_hue(a)?,
+            parse_fraction(b, 100.0)?,
+            parse_fraction(c, 100.0)?,
+            alpha,
+        )),
+        // Lightness is 0 to 1 and a chroma of 100% equals 0.4
+        "oklch" => Some(Color::oklcha(
+            parse_fraction(a, 1.0)?,
+            match b.strip_suffix('%') {
+                Some(percent) => percent.parse::<f32>().ok()? / 100.0 * 0.4,
+                None => b.parse::<f32>().ok().filter(|chroma| chroma.is_finite())?,
+            },
+            parse_hue(c)?,
+            alpha,
+        )),
+        _ => None,
+    }
+}
+
+/// Parse a number or percentage into a fraction, plain numbers are divided by `max`
+fn parse_fraction(value: &str, max: f32) -> Option<f32> {
+    let fraction = match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok()? / 100.0,
+        None => value.parse::<f32>().ok()? / max,
+    };
+
+    fraction.is_finite().then_some(fraction.clamp(0.0, 1.0))
+}
+
+/// Parse a hue in degrees, `deg`, `rad` and `turn` units are supported
+fn parse_hue(value: &str) -> Option<f32> {
+    let degrees = if let Some(deg) = value.strip_suffix("deg") {
+        deg.parse::<f32>().ok()?
+    } else if let Some(rad) = value.strip_suffix("rad") {
+        rad.parse::<f32>().ok()?.to_degrees()
+    } else if let Some(turn) = value.strip_suffix("turn") {
+        turn.parse::<f32>().ok()? * 360.0
+    } else {
+        value.parse::<f32>().ok()?
+    };
+
+    degrees.is_finite().then_some(degrees.rem_euclid(360.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_colors() {
+        let orange = Color::srgb_u8(0xff, 0x88, 0x00);
+        assert_eq!(parse_css_color("#ff8800"), Some(orange));
+        assert_eq!(parse_css_color("#F80"), Some(orange));
+        assert_eq!(
+            parse_css_color("#ff880080"),
+            Some(Color::srgba_u8(0xff, 0x88, 0x00, 0x80))
+        );
+        assert_eq!(
+            parse_css_color("#f808"),
+            Some(Color::srgba_u8(0xff, 0x88, 0x00, 0x88))
+        );
+        assert_eq!(
+            parse_css_color("#ff88"),
+            Some(Color::srgba_u8(0xff, 0xff, 0x88, 0x88))
+        );
+        assert_eq!(parse_css_color("#ff88000"), None);
+        assert_eq!(parse_css_color("#ggg"), None);
+        assert_eq!(parse_css_color("#"), None);
+    }
+
+    #[test]
+    fn rgb_functions() {
+        let orange = Color::srgb_u8(0xff, 0x88, 0x00);
+        assert_eq!(parse_css_color("rgb(255, 136, 0)"), Some(orange));
+        assert_eq!(parse_css_color("rgb(255 136 0)"), Some(orange));
+        assert_eq!(parse_css_color(" RGB( 255 , 136 , 0 ) "), Some(orange));
+        assert_eq!(
+            parse_css_color("rgb(100% 0% 50%)"),
+            Some(Color::srgb(1.0, 0.0, 0.5))
+        );
+        assert_eq!(
+            parse_css_color("rgba(255, 136, 0, 0.5)"),
+            Some(orange.with_alpha(0.5))
+        );
+        assert_eq!(
+            parse_css_color("rgb(255 136 0 / 25%)"),
+            Some(orange.with_alpha(0.25))
+        );
+    }
+
+    #[test]
+    fn hsl_functions() {
+        assert_eq!(
+            parse_css_color("hsl(120, 50%, 25%)"),
+            Some(Color::hsl(120.0, 0.5, 0.25))
+        );
+        assert_eq!(
+            parse_css_color("hsla(120deg 50% 25% / 0.5)"),
+            Some(Color::hsla(120.0, 0.5, 0.25, 0.5))
+        );
+        assert_eq!(
+            parse_css_color("hsl(0.5turn 100% 50%)"),
+            Some(Color::hsl(180.0, 1.0, 0.5))
+        );
+        // Hues wrap around
+        assert_eq!(
+            parse_css_color("hsl(-90 100% 50%)"),
+            Some(Color::hsl(270.0, 1.0, 0.5))
+        );
+        assert_eq!(
+            parse_css_color("hsl(3.14159265rad 100% 50%)"),
+            Some(Color::hsl(std::f32::consts::PI.to_degrees(), 1.0, 0.5))
+        );
+    }
+
+    #[test]
+    fn oklch_function() {
+        assert_eq!(
+            parse_css_color("oklch(0.7 0.1 200)"),
+            Some(Color::oklch(0.7, 0.1, 200.0))
+        );
+        assert_eq!(
+            parse_css_color("oklch(70% 50% 200 / 50%)"),
+            Some(Color::oklcha(0.7, 0.2, 200.0, 0.5))
+        );
+    }
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(
+            parse_css_color("rebeccapurple"),
+            Some(css::REBECCA_PURPLE.into())
+        );
+        assert_eq!(parse_css_color("Red"), Some(css::RED.into()));
+        assert_eq!(parse_css_color("transparent"), Some(Color::NONE));
+        assert_eq!(parse_css_color("reddish"), None);
+    }
+
+    #[test]
+    fn out_of_range_channels_are_clamped() {
+        assert_eq!(
+            parse_css_color("rgb(300 -20 0 / 2)"),
+            Some(Color::srgba(1.0, 0.0, 0.0, 1.0))
+        );
+        assert_eq!(
+            parse_css_color("hsl(0 150% 50%)"),
+            Some(Color::hsl(0.0, 1.0, 0.5))
+        );
+    }
+
+    #[test]
+    fn malformed_colors() {
+        for value in [
+            "",
+            "rgb",
+            "rgb()",
+            "rgb(255, 0)",
+            "rgb(255, 0, 0, 1, 1)",
+            "rgb(255, 0, 0",
+            "rgb(red, 0, 0)",
+            "rgb(1e40, 0, 0)",
+            "rgb(NaN, 0, 0)",
+            "hsl(inf 50% 50%)",
+            "hsl(90grad 50% 50%)",
+            "oklch(0.5 1e40 0)",
+            "cmyk(0, 0, 0)",
+            "rgb(255, 0, 0) red",
+        ] {
+            assert_eq!(parse_css_color(value), None, "{value}");
+        }
+    }
+}
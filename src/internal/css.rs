@@ -0,0 +1,507 @@
+//! Declarations of inline `style` attributes, e.g. `width: 40%; background-color: #222`
+
+use bevy::prelude::*;
+use bevy::text::FontWeight;
+use bevy::text::LineBreak;
+use bevy::text::LineHeight;
+
+use crate::internal::color::parse_css_color;
+use crate::internal::tailwind::Style;
+use crate::internal::tailwind::StyleContext;
+use crate::internal::tailwind::StyleError;
+use crate::internal::tailwind::arbitrary_length;
+use crate::internal::tailwind::arbitrary_opacity;
+use crate::internal::tailwind::font_size;
+use crate::internal::tailwind::grid_tracks;
+use crate::internal::tailwind::handle_unsupported;
+
+/// Split a style attribute into its properties and values
+///
+/// Properties are lowercase, `!important` is ignored as inline styles take precedence anyway.
+pub fn declarations(style: &str) -> impl Iterator<Item = (String, &str)> {
+    style.split(';').filter_map(|declaration| {
+        let (property, value) = declaration.split_once(':')?;
+        let value = value.trim();
+        let value = value.strip_suffix("!important").unwrap_or(value).trim_end();
+        Some((property.trim().to_ascii_lowercase(), value))
+    })
+}
+
+/// Split a value into its parts at whitespace outside of parentheses
+///
+/// `1px solid rgb(255, 255, 255)` has three parts.
+fn tokens(value: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    let mut depth = 0usize;
+    let mut start = None;
+
+    for (index, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if c.is_ascii_whitespace() && depth == 0 => {
+                if let Some(start) = start.take() {
+                    tokens.push(&value[start..index]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(index);
+    }
+    if let Some(start) = start {
+        tokens.push(&value[start..]);
+    }
+
+    tokens
+}
+
+/// Length of a property, `auto` or a value supported by [`arbitrary_length`]
+fn length(value: &str, rem: f32) -> Option<Val> {
+    match value {
+        "auto" => Some(auto()),
+        value => arbitrary_length(value, rem),
+    }
+}
+
+/// Lengths of the four edges of a shorthand like `margin: 4px 8px`, in the order of CSS
+fn edges(value: &str, rem: f32) -> Option<UiRect> {
+    let values = tokens(value)
+        .into_iter()
+        .map(|value| length(value, rem))
+        .collect::<Option<Vec<_>>>()?;
+
+    match values[..] {
+        [all] => Some(UiRect::all(all)),
+        [vertical, horizontal] => Some(UiRect::axes(horizontal, vertical)),
+        [top, horizontal, bottom] => Some(UiRect::new(horizontal, horizontal, top, bottom)),
+        [top, right, bottom, left] => Some(UiRect::new(left, right, top, bottom)),
+        _ => None,
+    }
+}
+
+impl Style {
+    /// Apply the declarations of a style attribute, they take precedence over the classes
+    ///
+    /// Unsupported properties and values are handled like unsupported classes.
+    pub(crate) fn apply_declarations(
+        &mut self,
+        style: &str,
+        ctx: &StyleContext,
+    ) -> Result<(), StyleError> {
+        for (property, value) in declarations(style) {
+            if self.apply_declaration(&property, value, ctx).is_none() {
+                handle_unsupported(
+                    StyleError::UnsupportedDeclaration(format!("{property}: {value}")),
+                    ctx.unsupported,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply a single declaration, `None` if the property or the value isn't supported
+    fn apply_declaration(&mut self, property: &str, value: &str, ctx: &StyleContext) -> Option<()> {
+        let rem = ctx.rem;
+
+        match property {
+            "width" => self.width = length(value, rem)?,
+            "min-width" => self.min_width = length(value, rem)?,
+            "max-width" => self.max_width = length(value, rem)?,
+            "height" => self.height = length(value, rem)?,
+            "min-height" => self.min_height = length(value, rem)?,
+            "max-height" => self.max_height = length(value, rem)?,
+            "flex-basis" => self.flex_basis = length(value, rem)?,
+
+            "top" => self.top = length(value, rem)?,
+            "right" => self.right = length(value, rem)?,
+            "bottom" => self.bottom = length(value, rem)?,
+            "left" => self.left = length(value, rem)?,
+
+            "margin" => self.margin = edges(value, rem)?,
+            "margin-top" => self.margin.top = length(value, rem)?,
+            "margin-right" => self.margin.right = length(value, rem)?,
+            "margin-bottom" => self.margin.bottom = length(value, rem)?,
+            "margin-left" => self.margin.left = length(value, rem)?,
+            "padding" => self.padding = edges(value, rem)?,
+            "padding-top" => self.padding.top = length(value, rem)?,
+            "padding-right" => self.padding.right = length(value, rem)?,
+            "padding-bottom" => self.padding.bottom = length(value, rem)?,
+            "padding-left" => self.padding.left = length(value, rem)?,
+            "border-width" => self.border = edges(value, rem)?,
+            "border-top-width" => self.border.top = length(value, rem)?,
+            "border-right-width" => self.border.right = length(value, rem)?,
+            "border-bottom-width" => self.border.bottom = length(value, rem)?,
+            "border-left-width" => self.border.left = length(value, rem)?,
+
+            "gap" => {
+                let mut values = tokens(value).into_iter();
+                let row = length(values.next()?, rem)?;
+                let column = match values.next() {
+                    Some(column) => length(column, rem)?,
+                    None => row,
+                };
+                if values.next().is_some() {
+                    return None;
+                }
+                self.row_gap = row;
+                self.column_gap = column;
+            }
+            "row-gap" => self.row_gap = length(value, rem)?,
+            "column-gap" => self.column_gap = length(value, rem)?,
+
+            "display" => {
+                self.display = match value {
+                    "flex" => Display::Flex,
+                    "grid" => Display::Grid,
+                    "block" => Display::Block,
+                    "none" => Display::None,
+                    _ => return None,
+                }
+            }
+            "position" => {
+                self.position = match value {
+                    "relative" => PositionType::Relative,
+                    "absolute" => PositionType::Absolute,
+                    _ => return None,
+                }
+            }
+            "visibility" => {
+                self.visibility = match value {
+                    "visible" => Visibility::Visible,
+                    "hidden" => Visibility::Hidden,
+                    _ => return None,
+                }
+            }
+            "z-index" => {
+                self.z_index = match value {
+                    "auto" => ZIndex(0),
+                    value => ZIndex(value.parse().ok()?),
+                }
+            }
+
+            "flex-direction" => {
+                self.flex_direction = match value {
+                    "row" => FlexDirection::Row,
+                    "column" => FlexDirection::Column,
+                    "row-reverse" => FlexDirection::RowReverse,
+                    "column-reverse" => FlexDirection::ColumnReverse,
+                    _ => return None,
+                }
+            }
+            "justify-content" => {
+                self.justify_content = match value {
+                    "normal" => JustifyContent::Default,
+                    "start" | "flex-start" => JustifyContent::FlexStart,
+                    "end" | "flex-end" => JustifyContent::FlexEnd,
+                    "center" => JustifyContent::Center,
+                    "space-between" => JustifyContent::SpaceBetween,
+                    "space-around" => JustifyContent::SpaceAround,
+                    "space-evenly" => JustifyContent::SpaceEvenly,
+                    "stretch" => JustifyContent::Stretch,
+                    _ => return None,
+                }
+            }
+            "align-content" => {
+                self.align_content = match value {
+                    "normal" => AlignContent::Default,
+                    "start" | "flex-start" => AlignContent::FlexStart,
+                    "end" | "flex-end" => AlignContent::FlexEnd,
+                    "center" => AlignContent::Center,
+                    "space-between" => AlignContent::SpaceBetween,
+                    "space-around" => AlignContent::SpaceAround,
+                    "space-evenly" => AlignContent::SpaceEvenly,
+                    "stretch" => AlignContent::Stretch,
+                    _ => return None,
+                }
+            }
+            "align-items" => {
+                self.align_items = match value {
+                    "normal" => AlignItems::Default,
+                    "start" | "flex-start" => AlignItems::FlexStart,
+                    "end" | "flex-end" => AlignItems::FlexEnd,
+                    "center" => AlignItems::Center,
+                    "stretch" => AlignItems::Stretch,
+                    "baseline" => AlignItems::Baseline,
+                    _ => return None,
+                }
+            }
+            "align-self" => {
+                self.align_self = match value {
+                    "auto" => AlignSelf::Auto,
+                    "start" | "flex-start" => AlignSelf::FlexStart,
+                    "end" | "flex-end" => AlignSelf::FlexEnd,
+                    "center" => AlignSelf::Center,
+                    "stretch" => AlignSelf::Stretch,
+                    "baseline" => AlignSelf::Baseline,
+                    _ => return None,
+                }
+            }
+            "justify-items" => {
+                self.justify_items = match value {
+                    "normal" => JustifyItems::Default,
+                    "start" => JustifyItems::Start,
+                    "end" => JustifyItems::End,
+                    "center" => JustifyItems::Center,
+                    "stretch" => JustifyItems::Stretch,
+                    _ => return None,
+                }
+            }
+            "justify-self" => {
+                self.justify_self = match value {
+                    "auto" => JustifySelf::Auto,
+                    "start" => JustifySelf::Start,
+                    "end" => JustifySelf::End,
+                    "center" => JustifySelf::Center,
+                    "stretch" => JustifySelf::Stretch,
+                    _ => return None,
+                }
+            }
+            "grid-template-columns" => {
+                self.grid_template_columns = grid_tracks(tokens(value).into_iter(), rem)?
+            }
+            "grid-template-rows" => {
+                self.grid_template_rows = grid_tracks(tokens(value).into_iter(), rem)?
+            }
+
+            "background-color" | "background" => {
+                self.background_color = BackgroundColor(parse_css_color(value)?)
+            }
+            "color" => self.text_color = TextColor(parse_css_color(value)?),
+            "border-color" => self.border_color = BorderColor::all(parse_css_color(value)?),
+            "border" => {
+                // Width, style and color in any order, the style is ignored
+                for part in tokens(value) {
+                    if let Some(width) = arbitrary_length(part, rem) {
+                        self.border = UiRect::all(width);
+                    } else if let Some(color) = parse_css_color(part) {
+                        self.border_color = BorderColor::all(color);
+                    } else if !matches!(part, "solid" | "none") {
+                        return None;
+                    }
+                }
+            }
+            "border-radius" => {
+                let radii = tokens(value)
+                    .into_iter()
+                    .map(|radius| arbitrary_length(radius, rem))
+                    .collect::<Option<Vec<_>>>()?;
+                // Missing corners are the same as the opposite corner, like in CSS
+                self.border_radius = match radii[..] {
+                    [all] => BorderRadius::all(all),
+                    [top_left, top_right] => {
+                        BorderRadius::new(top_left, top_right, top_left, top_right)
+                    }
+                    [top_left, top_right, bottom_right] => {
+                        BorderRadius::new(top_left, top_right, bottom_right, top_right)
+                    }
+                    [top_left, top_right, bottom_right, bottom_left] => {
+                        BorderRadius::new(top_left, top_right, bottom_right, bottom_left)
+                    }
+                    _ => return None,
+                };
+            }
+            "outline-width" => self.outline_width = arbitrary_length(value, rem)?,
+            "outline-offset" => self.outline_offset = arbitrary_length(value, rem)?,
+            "outline-color" => self.outline_color = Some(parse_css_color(value)?),
+            "opacity" => self.opacity = arbitrary_opacity(value)?,
+
+            "font-size" => {
+                self.text_font.font_size = font_size(value, rem, self.text_font.font_size)?
+            }
+            "line-height" => {
+                self.line_height = match value {
+                    "normal" => LineHeight::default(),
+                    value => match value.parse::<f32>() {
+                        Ok(factor) => LineHeight::RelativeToFont(factor),
+                        Err(_) => match arbitrary_length(value, rem)? {
+                            Val::Px(pixels) => LineHeight::Px(pixels),
+                            Val::Percent(percent) => LineHeight::RelativeToFont(percent / 100.0),
+                            _ => return None,
+                        },
+                    },
+                }
+            }
+            "font-weight" => {
+                self.text_font.weight = match value {
+                    "normal" => FontWeight::NORMAL,
+                    "bold" => FontWeight::BOLD,
+                    value => FontWeight(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|weight| (1..=1000).contains(weight))?,
+                    ),
+                }
+            }
+            "font-style" => {
+                self.italic = match value {
+                    "normal" => false,
+                    "italic" | "oblique" => true,
+                    _ => return None,
+                }
+            }
+            "text-align" => {
+                self.text_layout.justify = match value {
+                    "left" | "start" => Justify::Left,
+                    "right" | "end" => Justify::Right,
+                    "center" => Justify::Center,
+                    "justify" => Justify::Justified,
+                    _ => return None,
+                }
+            }
+            "white-space" | "text-wrap" => {
+                self.text_layout.linebreak = match value {
+                    "normal" | "wrap" => LineBreak::WordBoundary,
+                    "nowrap" => LineBreak::NoWrap,
+                    _ => return None,
+                }
+            }
+            "text-decoration" | "text-decoration-line" => {
+                let mut underline = false;
+                let mut strikethrough = false;
+                for part in tokens(value) {
+                    match part {
+                        "underline" => underline = true,
+                        "line-through" => strikethrough = true,
+                        "none" => {}
+                        _ => return None,
+                    }
+                }
+                self.underline = underline;
+                self.strikethrough = strikethrough;
+            }
+
+            _ => return None,
+        }
+
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::tailwind::UnsupportedClasses;
+    use crate::internal::testing::context;
+
+    fn apply(style: &str) -> Result<Style, StyleError> {
+        let mut result = Style::default();
+        result.apply_declarations(style, &context())?;
+        Ok(result)
+    }
+
+    #[test]
+    fn split_declarations() {
+        let split =
+            declarations(" Width : 40% ;; color:red !important; broken ; ").collect::<Vec<_>>();
+        assert_eq!(
+            split,
+            [("width".to_string(), "40%"), ("color".to_string(), "red")]
+        );
+        assert_eq!(declarations("").count(), 0);
+    }
+
+    #[test]
+    fn split_tokens() {
+        assert_eq!(tokens("1px  solid\tred"), ["1px", "solid", "red"]);
+        assert_eq!(
+            tokens("1px solid rgb(255, 0, 0)"),
+            ["1px", "solid", "rgb(255, 0, 0)"]
+        );
+        assert_eq!(
+            tokens("repeat(2, minmax(0, 1fr)) 10px"),
+            ["repeat(2, minmax(0, 1fr))", "10px"]
+        );
+        // Unbalanced parentheses don't swallow the rest
+        assert_eq!(tokens(") a b"), [")", "a", "b"]);
+        assert!(tokens("   ").is_empty());
+    }
+
+    #[test]
+    fn edge_shorthands() {
+        assert_eq!(edges("4px", 16.0), Some(UiRect::all(px(4))));
+        assert_eq!(edges("4px 1rem", 16.0), Some(UiRect::axes(px(16), px(4))));
+        assert_eq!(
+            edges("1px auto 3px", 16.0),
+            Some(UiRect::new(auto(), auto(), px(1), px(3)))
+        );
+        assert_eq!(
+            edges("1px 2px 3px 4px", 16.0),
+            Some(UiRect::new(px(4), px(2), px(1), px(3)))
+        );
+        assert_eq!(edges("", 16.0), None);
+        assert_eq!(edges("1px 2px 3px 4px 5px", 16.0), None);
+        assert_eq!(edges("1px wide", 16.0), None);
+    }
+
+    #[test]
+    fn border_radius_forms() {
+        let radius = |value: &str| {
+            apply(&format!("border-radius: {value}"))
+                .unwrap()
+                .border_radius
+        };
+        assert_eq!(radius("4px"), BorderRadius::all(px(4)));
+        assert_eq!(
+            radius("1px 2px"),
+            BorderRadius::new(px(1), px(2), px(1), px(2))
+        );
+        assert_eq!(
+            radius("1px 2px 3px"),
+            BorderRadius::new(px(1), px(2), px(3), px(2))
+        );
+        assert_eq!(
+            radius("1px 2px 3px 4px"),
+            BorderRadius::new(px(1), px(2), px(3), px(4))
+        );
+        assert!(apply("border-radius: 1px 2px 3px 4px 5px").is_err());
+        assert!(apply("border-radius: auto").is_err());
+    }
+
+    #[test]
+    fn border_shorthand() {
+        let style = apply("border: rgb(255, 0, 0) solid 2px").unwrap();
+        assert_eq!(style.border, UiRect::all(px(2)));
+        assert_eq!(
+            style.border_color,
+            BorderColor::all(Color::srgb(1.0, 0.0, 0.0))
+        );
+        assert!(apply("border: 2px dashed red").is_err());
+    }
+
+    #[test]
+    fn later_declarations_win() {
+        let style = apply("padding: 4px; padding-left: 8px; z-index: 5; opacity: 50%").unwrap();
+        assert_eq!(style.padding, UiRect::new(px(8), px(4), px(4), px(4)));
+        assert_eq!(style.opacity, 0.5);
+        assert_eq!(apply("z-index: auto").unwrap().z_index, ZIndex(0));
+        assert_eq!(style.z_index, ZIndex(5));
+    }
+
+    #[test]
+    fn unsupported_declarations() {
+        assert!(matches!(
+            apply("width: wide"),
+            Err(StyleError::UnsupportedDeclaration(declaration)) if declaration == "width: wide"
+        ));
+        assert!(matches!(
+            apply("float: left"),
+            Err(StyleError::UnsupportedDeclaration(declaration)) if declaration == "float: left"
+        ));
+
+        // Other declarations still apply if unsupported ones are ignored
+        for unsupported in [UnsupportedClasses::Warn, UnsupportedClasses::Ignore] {
+            let context = StyleContext {
+                unsupported,
+                ..context()
+            };
+            let mut style = Style::default();
+            style
+                .apply_declarations("float: left; width: 10px", &context)
+                .unwrap();
+            assert_eq!(style.width, px(10));
+        }
+    }
+}
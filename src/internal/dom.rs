@@ -1,18 +1,366 @@
+use std::collections::HashSet;
+
+use bevy::asset::AssetPath;
 use bevy::asset::LoadContext;
 use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
+use bevy::ui::InteractionDisabled;
+use log::warn;
 
+use crate::assets::HtmlUiLoadError;
+use crate::assets::HtmlUiLoaderSettings;
+use crate::bundle::HtmlAttributes;
+use crate::bundle::HtmlCustomElement;
 use crate::bundle::HtmlId;
+use crate::internal::markup::RawElement;
+use crate::internal::markup::RawNode;
+use crate::internal::tailwind::ElementState;
+use crate::internal::tailwind::SiblingPosition;
 use crate::internal::tailwind::Style;
+use crate::internal::tailwind::StyleContext;
+use crate::internal::tailwind::StyleError;
+use crate::internal::tailwind::VariantState;
+use crate::style::HtmlNodeStyle;
+use crate::style::SpawnContext;
+
+/// Position of a node in the source document
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub line: u32,
+    pub column: u32,
+    /// Partial or component template that the node was inlined from, `None` for nodes of the
+    /// document itself
+    pub source: Option<AssetPath<'static>>,
+}
+
+impl SourceLocation {
+    /// Path of the file containing the node, given the path of the document
+    pub fn path(&self, document: &AssetPath<'static>) -> AssetPath<'static> {
+        self.source.as_ref().unwrap_or(document).clone()
+    }
+}
+
+/// State that is shared while converting the nodes of a document
+pub(crate) struct ConvertContext<'a> {
+    pub path: &'a AssetPath<'static>,
+    pub settings: &'a HtmlUiLoaderSettings,
+    /// Tags of the custom elements registered by the app
+    pub custom_elements: &'a HashSet<String>,
+}
+
+/// Parse the classes of a node and attach the location of the node to errors
+///
+/// Text properties are inherited from the parent if there is one.
+fn parse_style(
+    classes: &str,
+    inline_style: &str,
+    location: &SourceLocation,
+    load_context: &LoadContext,
+    ctx: &StyleContext,
+    state: &VariantState,
+    parent: Option<&Style>,
+) -> Result<Style, HtmlUiLoadError> {
+    Style::parse(classes, inline_style, ctx, state, parent).map_err(|err| match err {
+        StyleError::UnknownFont(font) => HtmlUiLoadError::UnknownFont {
+            path: location.path(load_context.path()),
+            font,
+            line: location.line,
+            column: location.column,
+        },
+        StyleError::UnsupportedClass(class) => HtmlUiLoadError::UnsupportedClass {
+            path: location.path(load_context.path()),
+            class,
+            line: location.line,
+            column: location.column,
+        },
+        StyleError::UnsupportedDeclaration(declaration) => {
+            HtmlUiLoadError::UnsupportedDeclaration {
+                path: location.path(load_context.path()),
+                declaration,
+                line: location.line,
+                column: location.column,
+            }
+        }
+    })
+}
+
+/// Resolve the style of child nodes that follow `offset` other children, e.g. the spans of a
+/// text
+fn resolve_children(
+    children: &mut [XNode],
+    offset: usize,
+    load_context: &mut LoadContext,
+    ctx: &StyleContext,
+    parent: &Style,
+) -> Result<(), HtmlUiLoadError> {
+    let count = offset + children.len();
+    for (index, child) in children.iter_mut().enumerate() {
+        let position = SiblingPosition {
+            index: offset + index,
+            count,
+        };
+        child.resolve(load_context, ctx, Some(parent), position)?;
+    }
+
+    Ok(())
+}
+
+/// Return an error if the node is nested deeper than allowed
+fn check_depth(
+    node: &RawElement,
+    ctx: &ConvertContext,
+    depth: usize,
+) -> Result<(), HtmlUiLoadError> {
+    match ctx.settings.max_depth {
+        Some(max_depth) if depth >= max_depth => Err(HtmlUiLoadError::MaxDepthExceeded {
+            path: node.location.path(ctx.path),
+            max_depth,
+            line: node.location.line,
+            column: node.location.column,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Tags with a meaning of their own, which components can't replace
+pub(crate) const BUILT_IN_TAGS: &[&str] = &[
+    "html",
+    "head",
+    "body",
+    "title",
+    "meta",
+    "link",
+    "script",
+    "style",
+    "font",
+    "component",
+    "include",
+    "slot",
+    "div",
+    "section",
+    "nav",
+    "header",
+    "footer",
+    "main",
+    "aside",
+    "article",
+    "ul",
+    "ol",
+    "li",
+    "hr",
+    "br",
+    "p",
+    "span",
+    "label",
+    "b",
+    "strong",
+    "i",
+    "em",
+    "u",
+    "s",
+    "small",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "img",
+    "button",
+];
+
+/// Tags that are part of the text flow instead of creating a node of their own
+const INLINE_TAGS: &[&str] = &[
+    "span", "label", "b", "strong", "i", "em", "u", "s", "small", "br",
+];
+
+/// Classes applied to inline formatting elements before their own classes
+fn inline_classes(tag: &str) -> &'static str {
+    match tag {
+        "b" | "strong" => "font-bold",
+        "i" | "em" => "italic",
+        "u" => "underline",
+        "s" => "line-through",
+        "small" => "text-sm",
+        _ => "",
+    }
+}
+
+/// Add the inline content to the text of a node
+///
+/// Text after the first span is added as an anonymous span.
+fn push_inline_text(
+    content: &mut String,
+    spans: &mut Vec<XSpan>,
+    location: &SourceLocation,
+    text: String,
+) {
+    if text.is_empty() {
+        return;
+    }
+
+    match spans.last_mut() {
+        None => content.push_str(&text),
+        Some(span) if span.anonymous => span.content.push_str(&text),
+        Some(_) => spans.push(XSpan::anonymous(location.clone(), text)),
+    }
+}
+
+fn is_inline(node: &RawElement) -> bool {
+    INLINE_TAGS.contains(&node.name.as_str())
+}
+
+/// Whitespace as defined by HTML, in contrast to Rust this excludes `&nbsp;`
+fn is_html_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r' | '\x0c')
+}
+
+/// Collapses whitespace of consecutive text like HTML does
+///
+/// Runs of whitespace become a single space and whitespace at the start is removed.
+#[derive(Default)]
+struct WhitespaceCollapser {
+    after_text: bool,
+}
+
+impl WhitespaceCollapser {
+    /// A line break, whitespace following it is removed
+    fn line_break(&mut self) -> String {
+        self.after_text = false;
+        "\n".to_string()
+    }
+
+    fn collapse(&mut self, text: &str) -> String {
+        let mut collapsed = String::with_capacity(text.len());
+        for c in text.chars() {
+            if !is_html_whitespace(c) {
+                collapsed.push(c);
+                self.after_text = true;
+            } else if self.after_text {
+                collapsed.push(' ');
+                self.after_text = false;
+            }
+        }
+        collapsed
+    }
+}
+
+/// Convert the children of a block that may contain text interleaved with inline elements
+///
+/// Runs of inline content that contain text are grouped into an anonymous text node.
+/// Inline elements without surrounding text become text nodes of their own.
+fn convert_mixed_children(
+    node: &RawElement,
+    ctx: &ConvertContext,
+    depth: usize,
+) -> Result<Vec<XNode>, HtmlUiLoadError> {
+    let is_inline_content = |child: &RawNode| match child {
+        RawNode::Text(_) => true,
+        RawNode::Element(element) => is_inline(element),
+    };
+
+    let mut children = Vec::new();
+    for run in node
+        .children
+        .chunk_by(|a, b| is_inline_content(a) == is_inline_content(b))
+    {
+        let has_text = run.iter().any(|child| match child {
+            RawNode::Text(text) => !text.chars().all(is_html_whitespace),
+            RawNode::Element(_) => false,
+        });
+
+        if has_text {
+            let location = run
+                .iter()
+                .find_map(|child| match child {
+                    RawNode::Element(element) => Some(element.location.clone()),
+                    RawNode::Text(_) => None,
+                })
+                .unwrap_or_else(|| node.location.clone());
+            children.push(XNode::Text(XText::anonymous(
+                location,
+                run,
+                ctx,
+                depth + 1,
+            )?));
+            continue;
+        }
+
+        for child in run {
+            if let RawNode::Element(element) = child
+                && let Some(child) = XNode::convert(element, ctx, depth + 1)?
+            {
+                children.push(child);
+            }
+        }
+    }
+
+    Ok(children)
+}
+
+/// Classes applied to `<hr>` before its own classes
+const HR_CLASSES: &str = "w-full border-t border-[#808080] my-[8px]";
+
+/// Classes applied to `<br>` outside of text before its own classes
+///
+/// The node takes the full width to force a line break in wrapping layouts.
+const BR_CLASSES: &str = "w-full h-[0px]";
+
+/// Classes applied to headings before their own classes
+fn heading_classes(tag: &str) -> &'static str {
+    match tag {
+        "h1" => "text-4xl",
+        "h2" => "text-3xl",
+        "h3" => "text-2xl",
+        "h4" => "text-xl",
+        "h5" => "text-lg",
+        _ => "text-base",
+    }
+}
+
+/// Prepend the default classes of a tag to the classes of an element
+fn prepend_classes(defaults: &str, classes: &str) -> String {
+    format!("{defaults} {classes}")
+}
+
+/// Faces of a font registered under a single name
+#[derive(Debug, Clone, Default)]
+pub struct FontFamily {
+    pub regular: Option<Handle<Font>>,
+    pub bold: Option<Handle<Font>>,
+    pub italic: Option<Handle<Font>>,
+    pub bold_italic: Option<Handle<Font>>,
+}
+
+impl FontFamily {
+    /// Get the face that matches best, falling back to the regular face
+    pub fn face(&self, bold: bool, italic: bool) -> Option<Handle<Font>> {
+        let face = match (bold, italic) {
+            (true, true) => self
+                .bold_italic
+                .as_ref()
+                .or(self.bold.as_ref())
+                .or(self.italic.as_ref()),
+            (true, false) => self.bold.as_ref(),
+            (false, true) => self.italic.as_ref(),
+            (false, false) => None,
+        };
+
+        face.or(self.regular.as_ref()).cloned()
+    }
+}
 
 pub struct XFont;
 
 impl XFont {
+    /// Register a font declared by `<font name="..." src="..." variant="..."/>`
+    ///
+    /// The variant is one of `regular` (default), `bold`, `italic` and `bold-italic`.
     pub(crate) fn convert(
-        node: roxmltree::Node,
+        node: &RawElement,
         load_context: &mut LoadContext,
-        fonts: &mut HashMap<String, Handle<Font>>,
-        default_font: &mut Option<Handle<Font>>,
+        fonts: &mut HashMap<String, FontFamily>,
+        default_font: &mut FontFamily,
     ) {
         let name = node.attribute("name");
         let Some(src) = node.attribute("src") else {
@@ -21,16 +369,16 @@ impl XFont {
 
         let handle = load_context.load(src.to_string());
 
-        match name {
-            Some("default") | None => {
-                *default_font = Some(handle);
-            }
-            Some(name) if name == "default" => {
-                *default_font = Some(handle);
-            }
-            Some(name) => {
-                fonts.insert(name.to_string(), handle);
-            }
+        let family = match name {
+            Some("default") | None => default_font,
+            Some(name) => fonts.entry(name.to_string()).or_default(),
+        };
+
+        match node.attribute("variant") {
+            Some("bold") => family.bold = Some(handle),
+            Some("italic") => family.italic = Some(handle),
+            Some("bold-italic") => family.bold_italic = Some(handle),
+            _ => family.regular = Some(handle),
         }
     }
 }
@@ -41,42 +389,104 @@ pub enum XNode {
     Text(XText),
     Img(XImg),
     Button(XButton),
+    Custom(XCustom),
 }
 
 impl XNode {
-    pub fn convert(node: roxmltree::Node) -> Self {
-        match node.node_type() {
-            roxmltree::NodeType::Element => match node.tag_name().name().to_lowercase().as_str() {
-                "div" => Self::Div(XDiv::convert(node)),
-                "p" | "span" => Self::Text(XText::convert(node)),
-                "img" => Self::Img(XImg::convert(node)),
-                "button" => Self::Button(XButton::convert(node)),
-                _ => panic!("Unsupported tag: {}", node.tag_name().name()),
-            },
-            _ => panic!("Unsupported node type: {:?}", node.node_type()),
+    /// Convert an element of the document
+    ///
+    /// Returns `None` if the element is skipped as it is not supported
+    pub(crate) fn convert(
+        node: &RawElement,
+        ctx: &ConvertContext,
+        depth: usize,
+    ) -> Result<Option<Self>, HtmlUiLoadError> {
+        let location = node.location.clone();
+        check_depth(node, ctx, depth)?;
+
+        match node.name.as_str() {
+            "div" | "section" | "nav" | "header" | "footer" | "main" | "aside" | "article"
+            | "ul" | "ol" | "li" => Ok(Some(Self::Div(XDiv::convert(node, ctx, depth)?))),
+            "hr" => Ok(Some(Self::Div(
+                XDiv::convert(node, ctx, depth)?.with_default_classes(HR_CLASSES),
+            ))),
+            "br" => Ok(Some(Self::Div(
+                XDiv::convert(node, ctx, depth)?.with_default_classes(BR_CLASSES),
+            ))),
+            "p" | "span" | "label" => Ok(Some(Self::Text(XText::convert(node, ctx, depth)?))),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => Ok(Some(Self::Text(
+                XText::convert(node, ctx, depth)?.with_default_classes(heading_classes(&node.name)),
+            ))),
+            "img" => Ok(Some(Self::Img(XImg::convert(node, ctx, depth)?))),
+            "button" => Ok(Some(Self::Button(XButton::convert(node, ctx, depth)?))),
+            tag if ctx.custom_elements.contains(tag) => {
+                Ok(Some(Self::Custom(XCustom::convert(node, ctx, depth)?)))
+            }
+            // Metadata doesn't produce nodes
+            "head" | "title" | "meta" | "link" | "script" | "style" | "font" | "component" => {
+                Ok(None)
+            }
+            tag if ctx.settings.strict => Err(HtmlUiLoadError::UnsupportedTag {
+                path: location.path(ctx.path),
+                tag: tag.to_string(),
+                line: location.line,
+                column: location.column,
+            }),
+            _ if ctx.settings.unknown_tags_as_div => {
+                Ok(Some(Self::Div(XDiv::convert(node, ctx, depth)?)))
+            }
+            tag => {
+                warn!(
+                    "{}:{}:{}: Skipping unsupported tag: {tag}",
+                    location.path(ctx.path),
+                    location.line,
+                    location.column
+                );
+                Ok(None)
+            }
         }
     }
 
+    /// Resolve the style of the node and its children
+    ///
+    /// Text properties like color, font, font size, line height and alignment are
+    /// inherited from the parent, just like in CSS. Root nodes have no parent.
+    /// The position among the siblings decides structural variants like `first:` and whether
+    /// `space-*` and `divide-*` of the parent apply.
     pub fn resolve(
         &mut self,
         load_context: &mut LoadContext,
-        fonts: &HashMap<String, Handle<Font>>,
-        default_font: Option<Handle<Font>>,
-    ) {
+        ctx: &StyleContext,
+        parent: Option<&Style>,
+        position: SiblingPosition,
+    ) -> Result<(), HtmlUiLoadError> {
         match self {
-            XNode::Div(x) => x.resolve(load_context, fonts, default_font.clone()),
-            XNode::Text(x) => x.resolve(load_context, fonts, default_font.clone()),
-            XNode::Img(x) => x.resolve(load_context, fonts, default_font.clone()),
-            XNode::Button(x) => x.resolve(load_context, fonts, default_font.clone()),
+            XNode::Div(x) => x.resolve(load_context, ctx, parent, position),
+            XNode::Text(x) => x.resolve(load_context, ctx, parent, position),
+            XNode::Img(x) => x.resolve(load_context, ctx, parent, position),
+            XNode::Button(x) => x.resolve(load_context, ctx, parent, position),
+            XNode::Custom(x) => x.node.resolve(load_context, ctx, parent, position),
         }
     }
 
-    pub(crate) fn apply_to_entity(&self, commands: &mut EntityCommands) {
+    /// Position of the node in the source document
+    pub fn location(&self) -> &SourceLocation {
         match self {
-            XNode::Div(x) => x.apply_to_entity(commands),
-            XNode::Text(x) => x.apply_to_entity(commands),
-            XNode::Img(x) => x.apply_to_entity(commands),
-            XNode::Button(x) => x.apply_to_entity(commands),
+            XNode::Div(x) => &x.location,
+            XNode::Text(x) => &x.location,
+            XNode::Img(x) => &x.location,
+            XNode::Button(x) => &x.location,
+            XNode::Custom(x) => &x.node.location,
+        }
+    }
+
+    pub(crate) fn apply_to_entity(&self, commands: &mut EntityCommands, ctx: &SpawnContext) {
+        match self {
+            XNode::Div(x) => x.apply_to_entity(commands, ctx),
+            XNode::Text(x) => x.apply_to_entity(commands, ctx),
+            XNode::Img(x) => x.apply_to_entity(commands, ctx),
+            XNode::Button(x) => x.apply_to_entity(commands, ctx),
+            XNode::Custom(x) => x.apply_to_entity(commands, ctx),
         }
     }
 }
@@ -84,145 +494,501 @@ impl XNode {
 #[derive(Debug, Clone)]
 pub struct XDiv {
     pub id: Option<String>,
+    pub location: SourceLocation,
     pub classes: String,
+    /// Declarations of the `style` attribute
+    pub inline_style: String,
+    /// All attributes in the order of the document
+    pub attributes: Vec<(String, String)>,
     pub style: Style,
-    pub content: Option<String>,
     pub children: Vec<XNode>,
 }
 
 impl XDiv {
-    pub fn convert(node: roxmltree::Node) -> Self {
+    fn with_default_classes(mut self, defaults: &str) -> Self {
+        self.classes = prepend_classes(defaults, &self.classes);
+        self
+    }
+
+    pub(crate) fn convert(
+        node: &RawElement,
+        ctx: &ConvertContext,
+        depth: usize,
+    ) -> Result<Self, HtmlUiLoadError> {
+        let location = node.location.clone();
         let mut id = None;
         let mut classes = "".to_string();
-        let content = node
-            .text()
-            .map(|t| t.trim())
-            .filter(|t| !t.is_empty())
-            .map(|t| t.to_string());
-        let mut children = Vec::new();
+        let mut inline_style = String::new();
 
-        for attribute in node.attributes() {
-            match attribute.name() {
-                "class" => classes = attribute.value().to_string(),
-                "id" => id = Some(attribute.value().to_string()),
+        for (name, value) in &node.attributes {
+            match name.as_str() {
+                "class" => classes = value.clone(),
+                "style" => inline_style = value.clone(),
+                "id" => id = Some(value.clone()),
                 _ => {}
             }
         }
 
-        for child in node.children() {
-            if child.is_element() {
-                children.push(XNode::convert(child));
-            }
-        }
+        let children = convert_mixed_children(node, ctx, depth)?;
 
-        Self {
+        Ok(Self {
             id,
+            location,
             classes,
+            inline_style,
+            attributes: node.attributes.clone(),
             style: Style::default(),
-            content,
             children,
-        }
+        })
     }
 
     pub fn resolve(
         &mut self,
         load_context: &mut LoadContext,
-        fonts: &HashMap<String, Handle<Font>>,
-        default_font: Option<Handle<Font>>,
-    ) {
-        self.style = Style::parse(&self.classes, fonts, default_font.clone());
-        for child in &mut self.children {
-            child.resolve(load_context, fonts, default_font.clone());
-        }
+        ctx: &StyleContext,
+        parent: Option<&Style>,
+        position: SiblingPosition,
+    ) -> Result<(), HtmlUiLoadError> {
+        self.style = parse_style(
+            &self.classes,
+            &self.inline_style,
+            &self.location,
+            load_context,
+            ctx,
+            &VariantState {
+                position,
+                ..Default::default()
+            },
+            parent,
+        )?;
+        resolve_children(&mut self.children, 0, load_context, ctx, &self.style)?;
+
+        Ok(())
     }
 
-    fn apply_to_entity(&self, commands: &mut EntityCommands) {
-        commands.insert(self.style.to_node());
+    fn apply_to_entity(&self, commands: &mut EntityCommands, ctx: &SpawnContext) {
+        HtmlNodeStyle::new(&self.style, ctx)
+            .with_inline_style(&self.inline_style)
+            .spawn(commands, &self.classes);
         if let Some(id) = &self.id {
             commands.insert(HtmlId(id.clone()));
         }
-
-        if let Some(content) = &self.content {
-            commands.with_child(Text::new(content));
+        if !self.attributes.is_empty() {
+            commands.insert(HtmlAttributes(self.attributes.clone()));
         }
 
         commands.with_children(|parent| {
             for child in &self.children {
                 let mut child_entity_commands = parent.spawn_empty();
-                child.apply_to_entity(&mut child_entity_commands);
+                child.apply_to_entity(&mut child_entity_commands, ctx);
             }
         });
     }
 }
 
+/// Element registered by the app, spawned like a `<div>` and built further by its function
+#[derive(Debug, Clone)]
+pub struct XCustom {
+    /// Lowercase tag name
+    pub tag: String,
+    pub node: XDiv,
+}
+
+impl XCustom {
+    pub(crate) fn convert(
+        node: &RawElement,
+        ctx: &ConvertContext,
+        depth: usize,
+    ) -> Result<Self, HtmlUiLoadError> {
+        Ok(Self {
+            tag: node.name.clone(),
+            node: XDiv::convert(node, ctx, depth)?,
+        })
+    }
+
+    fn apply_to_entity(&self, commands: &mut EntityCommands, ctx: &SpawnContext) {
+        self.node.apply_to_entity(commands, ctx);
+        commands.insert(HtmlCustomElement {
+            tag: self.tag.clone(),
+        });
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct XText {
     pub id: Option<String>,
+    pub location: SourceLocation,
     pub classes: String,
+    /// Declarations of the `style` attribute
+    pub inline_style: String,
+    /// All attributes in the order of the document
+    pub attributes: Vec<(String, String)>,
     pub style: Style,
-    pub content: Option<String>,
+    /// Text before the first span
+    pub content: String,
+    /// Inline content following the leading text
+    pub spans: Vec<XSpan>,
+    /// Nodes that are not part of the text flow
+    ///
+    /// If the element contains block elements, these are the block elements and anonymous
+    /// text nodes for the content around them, while the node has no text of its own.
     pub children: Vec<XNode>,
 }
 
 impl XText {
-    pub fn convert(node: roxmltree::Node) -> Self {
+    fn with_default_classes(mut self, defaults: &str) -> Self {
+        self.classes = prepend_classes(defaults, &self.classes);
+        self
+    }
+
+    pub(crate) fn convert(
+        node: &RawElement,
+        ctx: &ConvertContext,
+        depth: usize,
+    ) -> Result<Self, HtmlUiLoadError> {
+        let location = node.location.clone();
         let mut id = None;
         let mut classes = String::new();
-        let content = node
-            .text()
-            .map(|t| t.trim())
-            .filter(|t| !t.is_empty())
-            .map(|t| t.to_string());
-        let mut children = Vec::new();
+        let mut inline_style = String::new();
 
-        for attribute in node.attributes() {
-            match attribute.name() {
-                "class" => classes = attribute.value().to_string(),
-                "id" => id = Some(attribute.value().to_string()),
+        for (name, value) in &node.attributes {
+            match name.as_str() {
+                "class" => classes = value.clone(),
+                "style" => inline_style = value.clone(),
+                "id" => id = Some(value.clone()),
                 _ => {}
             }
         }
-        for child in node.children() {
-            if child.is_element() {
-                children.push(XNode::convert(child));
-            }
-        }
 
-        Self {
+        let mut text = Self {
             id,
+            location,
             classes,
+            inline_style,
+            attributes: node.attributes.clone(),
             style: Style::default(),
-            content,
-            children,
+            content: String::new(),
+            spans: vec![],
+            children: vec![],
+        };
+
+        // Block elements can't be part of a text, so the content is split into anonymous
+        // text nodes around them to keep the order of the document
+        match node.elements().find(|element| !is_inline(element)) {
+            Some(block) if ctx.settings.strict => {
+                return Err(HtmlUiLoadError::BlockInsideText {
+                    path: block.location.path(ctx.path),
+                    tag: block.name.clone(),
+                    line: block.location.line,
+                    column: block.location.column,
+                });
+            }
+            Some(_) => text.children = convert_mixed_children(node, ctx, depth)?,
+            None => text.convert_content(&node.children, ctx, depth)?,
         }
+
+        Ok(text)
+    }
+
+    /// Text node without an element of its own, wrapping inline content of a block
+    fn anonymous(
+        location: SourceLocation,
+        content: &[RawNode],
+        ctx: &ConvertContext,
+        depth: usize,
+    ) -> Result<Self, HtmlUiLoadError> {
+        let mut text = Self {
+            id: None,
+            location,
+            classes: String::new(),
+            inline_style: String::new(),
+            attributes: vec![],
+            style: Style::default(),
+            content: String::new(),
+            spans: vec![],
+            children: vec![],
+        };
+        text.convert_content(content, ctx, depth)?;
+
+        Ok(text)
+    }
+
+    fn convert_content(
+        &mut self,
+        content: &[RawNode],
+        ctx: &ConvertContext,
+        depth: usize,
+    ) -> Result<(), HtmlUiLoadError> {
+        let mut whitespace = WhitespaceCollapser::default();
+
+        for child in content {
+            match child {
+                RawNode::Text(text) => {
+                    let text = whitespace.collapse(text);
+                    push_inline_text(&mut self.content, &mut self.spans, &self.location, text);
+                }
+                RawNode::Element(element) if element.name == "br" => {
+                    let text = whitespace.line_break();
+                    push_inline_text(&mut self.content, &mut self.spans, &self.location, text);
+                }
+                RawNode::Element(element) if is_inline(element) => {
+                    self.spans
+                        .push(XSpan::convert(element, ctx, depth + 1, &mut whitespace)?);
+                }
+                RawNode::Element(element) => {
+                    if let Some(child) = XNode::convert(element, ctx, depth + 1)? {
+                        self.children.push(child);
+                    }
+                }
+            }
+        }
+
+        // Trailing whitespace is removed from the end of the text
+        let mut texts = vec![&mut self.content];
+        for span in &mut self.spans {
+            span.collect_texts(&mut texts);
+        }
+        for text in texts.into_iter().rev() {
+            text.truncate(text.trim_end_matches(' ').len());
+            if !text.is_empty() {
+                break;
+            }
+        }
+        XSpan::remove_empty_runs(&mut self.spans);
+
+        Ok(())
     }
 
     pub fn resolve(
         &mut self,
         load_context: &mut LoadContext,
-        fonts: &HashMap<String, Handle<Font>>,
-        default_font: Option<Handle<Font>>,
-    ) {
-        self.style = Style::parse(&self.classes, fonts, default_font.clone());
-        for child in &mut self.children {
-            child.resolve(load_context, fonts, default_font.clone());
+        ctx: &StyleContext,
+        parent: Option<&Style>,
+        position: SiblingPosition,
+    ) -> Result<(), HtmlUiLoadError> {
+        self.style = parse_style(
+            &self.classes,
+            &self.inline_style,
+            &self.location,
+            load_context,
+            ctx,
+            &VariantState {
+                position,
+                ..Default::default()
+            },
+            parent,
+        )?;
+        // Spans are spawned before the other children
+        let count = self.spans.len() + self.children.len();
+        for (index, span) in self.spans.iter_mut().enumerate() {
+            span.resolve(
+                load_context,
+                ctx,
+                &self.style,
+                SiblingPosition { index, count },
+            )?;
         }
+        resolve_children(
+            &mut self.children,
+            self.spans.len(),
+            load_context,
+            ctx,
+            &self.style,
+        )?;
+
+        Ok(())
     }
 
-    fn apply_to_entity(&self, commands: &mut EntityCommands) {
-        commands.insert(self.style.to_node());
+    fn apply_to_entity(&self, commands: &mut EntityCommands, ctx: &SpawnContext) {
+        HtmlNodeStyle::new(&self.style, ctx)
+            .with_inline_style(&self.inline_style)
+            .spawn(commands, &self.classes);
         if let Some(id) = &self.id {
             commands.insert(HtmlId(id.clone()));
         }
+        if !self.attributes.is_empty() {
+            commands.insert(HtmlAttributes(self.attributes.clone()));
+        }
 
-        if let Some(content) = &self.content {
-            commands.insert(Text::new(content));
+        if !self.content.is_empty() || !self.spans.is_empty() {
+            commands.insert(Text::new(self.content.clone()));
         }
 
         commands.with_children(|parent| {
+            for span in &self.spans {
+                let mut span_commands = parent.spawn_empty();
+                span.apply_to_entity(&mut span_commands, ctx);
+            }
             for child in &self.children {
                 let mut child_entity_commands = parent.spawn_empty();
-                child.apply_to_entity(&mut child_entity_commands);
+                child.apply_to_entity(&mut child_entity_commands, ctx);
+            }
+        });
+    }
+}
+
+/// Inline element or run of text inside of a text node
+#[derive(Debug, Clone)]
+pub struct XSpan {
+    pub id: Option<String>,
+    pub location: SourceLocation,
+    pub classes: String,
+    /// Declarations of the `style` attribute
+    pub inline_style: String,
+    /// All attributes in the order of the document
+    pub attributes: Vec<(String, String)>,
+    pub style: Style,
+    /// Text before the first nested span
+    pub content: String,
+    pub spans: Vec<XSpan>,
+    /// Whether the span was created for a run of text instead of an element
+    pub anonymous: bool,
+}
+
+impl XSpan {
+    fn convert(
+        node: &RawElement,
+        ctx: &ConvertContext,
+        depth: usize,
+        whitespace: &mut WhitespaceCollapser,
+    ) -> Result<Self, HtmlUiLoadError> {
+        check_depth(node, ctx, depth)?;
+
+        let location = node.location.clone();
+        let mut id = None;
+        let mut classes = String::new();
+        let mut inline_style = String::new();
+
+        for (name, value) in &node.attributes {
+            match name.as_str() {
+                "class" => classes = value.clone(),
+                "style" => inline_style = value.clone(),
+                "id" => id = Some(value.clone()),
+                _ => {}
+            }
+        }
+        classes = prepend_classes(inline_classes(&node.name), &classes);
+
+        let mut content = String::new();
+        let mut spans: Vec<XSpan> = Vec::new();
+
+        for child in &node.children {
+            match child {
+                RawNode::Text(text) => {
+                    let text = whitespace.collapse(text);
+                    push_inline_text(&mut content, &mut spans, &location, text);
+                }
+                RawNode::Element(element) if element.name == "br" => {
+                    let text = whitespace.line_break();
+                    push_inline_text(&mut content, &mut spans, &location, text);
+                }
+                RawNode::Element(element) if is_inline(element) => {
+                    spans.push(XSpan::convert(element, ctx, depth + 1, whitespace)?);
+                }
+                RawNode::Element(element) => {
+                    warn!(
+                        "{}:{}:{}: Skipping <{}> inside of text",
+                        element.location.path(ctx.path),
+                        element.location.line,
+                        element.location.column,
+                        element.name
+                    );
+                }
+            }
+        }
+
+        Ok(Self {
+            id,
+            location,
+            classes,
+            inline_style,
+            attributes: node.attributes.clone(),
+            style: Style::default(),
+            content,
+            spans,
+            anonymous: false,
+        })
+    }
+
+    fn anonymous(location: SourceLocation, content: String) -> Self {
+        Self {
+            id: None,
+            location,
+            classes: String::new(),
+            inline_style: String::new(),
+            attributes: vec![],
+            style: Style::default(),
+            content,
+            spans: vec![],
+            anonymous: true,
+        }
+    }
+
+    /// Remove runs of text that became empty when trimming whitespace
+    fn remove_empty_runs(spans: &mut Vec<XSpan>) {
+        spans.retain(|span| !span.anonymous || !span.content.is_empty());
+        for span in spans {
+            Self::remove_empty_runs(&mut span.spans);
+        }
+    }
+
+    /// Collect the text of this span and its descendants in document order
+    fn collect_texts<'a>(&'a mut self, texts: &mut Vec<&'a mut String>) {
+        texts.push(&mut self.content);
+        for span in &mut self.spans {
+            span.collect_texts(texts);
+        }
+    }
+
+    fn resolve(
+        &mut self,
+        load_context: &mut LoadContext,
+        ctx: &StyleContext,
+        parent: &Style,
+        position: SiblingPosition,
+    ) -> Result<(), HtmlUiLoadError> {
+        self.style = parse_style(
+            &self.classes,
+            &self.inline_style,
+            &self.location,
+            load_context,
+            ctx,
+            &VariantState {
+                position,
+                ..Default::default()
+            },
+            Some(parent),
+        )?;
+        let count = self.spans.len();
+        for (index, span) in self.spans.iter_mut().enumerate() {
+            span.resolve(
+                load_context,
+                ctx,
+                &self.style,
+                SiblingPosition { index, count },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn apply_to_entity(&self, commands: &mut EntityCommands, ctx: &SpawnContext) {
+        commands.insert(TextSpan::new(self.content.clone()));
+        HtmlNodeStyle::new(&self.style, ctx)
+            .with_inline_style(&self.inline_style)
+            .span()
+            .spawn(commands, &self.classes);
+        if let Some(id) = &self.id {
+            commands.insert(HtmlId(id.clone()));
+        }
+        if !self.attributes.is_empty() {
+            commands.insert(HtmlAttributes(self.attributes.clone()));
+        }
+
+        commands.with_children(|parent| {
+            for span in &self.spans {
+                let mut span_commands = parent.spawn_empty();
+                span.apply_to_entity(&mut span_commands, ctx);
             }
         });
     }
@@ -231,60 +997,89 @@ impl XText {
 #[derive(Debug, Clone)]
 pub struct XImg {
     pub id: Option<String>,
+    pub location: SourceLocation,
     pub src: String,
     pub classes: String,
+    /// Declarations of the `style` attribute
+    pub inline_style: String,
+    /// All attributes in the order of the document
+    pub attributes: Vec<(String, String)>,
     pub style: Style,
     pub image_handle: Handle<Image>,
     pub children: Vec<XNode>,
 }
 
 impl XImg {
-    pub fn convert(node: roxmltree::Node) -> Self {
+    pub(crate) fn convert(
+        node: &RawElement,
+        ctx: &ConvertContext,
+        depth: usize,
+    ) -> Result<Self, HtmlUiLoadError> {
+        let location = node.location.clone();
         let mut id = None;
         let mut src = "".to_string();
         let mut classes = "".to_string();
+        let mut inline_style = String::new();
         let mut children = Vec::new();
 
-        for attribute in node.attributes() {
-            match attribute.name() {
-                "src" => src = attribute.value().to_string(),
-                "class" => classes = attribute.value().to_string(),
-                "id" => id = Some(attribute.value().to_string()),
+        for (name, value) in &node.attributes {
+            match name.as_str() {
+                "src" => src = value.clone(),
+                "class" => classes = value.clone(),
+                "style" => inline_style = value.clone(),
+                "id" => id = Some(value.clone()),
                 _ => {}
             }
         }
 
-        for child in node.children() {
-            if child.is_element() {
-                children.push(XNode::convert(child));
+        for child in node.elements() {
+            if let Some(child) = XNode::convert(child, ctx, depth + 1)? {
+                children.push(child);
             }
         }
 
-        Self {
+        Ok(Self {
             id,
+            location,
             src,
             classes,
+            inline_style,
+            attributes: node.attributes.clone(),
             style: Style::default(),
             image_handle: Handle::default(),
             children,
-        }
+        })
     }
 
     pub fn resolve(
         &mut self,
         load_context: &mut LoadContext,
-        fonts: &HashMap<String, Handle<Font>>,
-        default_font: Option<Handle<Font>>,
-    ) {
+        ctx: &StyleContext,
+        parent: Option<&Style>,
+        position: SiblingPosition,
+    ) -> Result<(), HtmlUiLoadError> {
         self.image_handle = load_context.load(&self.src);
-        self.style = Style::parse(&self.classes, fonts, default_font.clone());
-        for child in &mut self.children {
-            child.resolve(load_context, fonts, default_font.clone());
-        }
+        self.style = parse_style(
+            &self.classes,
+            &self.inline_style,
+            &self.location,
+            load_context,
+            ctx,
+            &VariantState {
+                position,
+                ..Default::default()
+            },
+            parent,
+        )?;
+        resolve_children(&mut self.children, 0, load_context, ctx, &self.style)?;
+
+        Ok(())
     }
 
-    fn apply_to_entity(&self, commands: &mut EntityCommands) {
-        commands.insert(self.style.to_node());
+    fn apply_to_entity(&self, commands: &mut EntityCommands, ctx: &SpawnContext) {
+        HtmlNodeStyle::new(&self.style, ctx)
+            .with_inline_style(&self.inline_style)
+            .spawn(commands, &self.classes);
         commands.insert(ImageNode {
             image: self.image_handle.clone(),
             ..Default::default()
@@ -292,11 +1087,14 @@ impl XImg {
         if let Some(id) = &self.id {
             commands.insert(HtmlId(id.clone()));
         }
+        if !self.attributes.is_empty() {
+            commands.insert(HtmlAttributes(self.attributes.clone()));
+        }
 
         commands.with_children(|parent| {
             for child in &self.children {
                 let mut child_commands = parent.spawn_empty();
-                child.apply_to_entity(&mut child_commands);
+                child.apply_to_entity(&mut child_commands, ctx);
             }
         });
     }
@@ -305,73 +1103,144 @@ impl XImg {
 #[derive(Debug, Clone)]
 pub struct XButton {
     pub id: Option<String>,
+    pub location: SourceLocation,
     pub classes: String,
+    /// Declarations of the `style` attribute
+    pub inline_style: String,
+    /// All attributes in the order of the document
+    pub attributes: Vec<(String, String)>,
+    /// Set by the `disabled` attribute, applies the `disabled:` variants
+    pub disabled: bool,
     pub style: Style,
-    pub content: Option<String>,
     pub children: Vec<XNode>,
 }
 impl XButton {
-    pub fn convert(node: roxmltree::Node) -> Self {
+    pub(crate) fn convert(
+        node: &RawElement,
+        ctx: &ConvertContext,
+        depth: usize,
+    ) -> Result<Self, HtmlUiLoadError> {
+        let location = node.location.clone();
         let mut id = None;
-        let content = node
-            .text()
-            .map(|t| t.trim())
-            .filter(|t| !t.is_empty())
-            .map(|t| t.to_string());
         let mut classes = "".to_string();
-        let mut children = Vec::new();
+        let mut inline_style = String::new();
+        let mut disabled = false;
 
-        for attribute in node.attributes() {
-            match attribute.name() {
-                "class" => classes = attribute.value().to_string(),
-                "id" => id = Some(attribute.value().to_string()),
+        for (name, value) in &node.attributes {
+            match name.as_str() {
+                "class" => classes = value.clone(),
+                "style" => inline_style = value.clone(),
+                "id" => id = Some(value.clone()),
+                "disabled" => disabled = true,
                 _ => {}
             }
         }
 
-        for child in node.children() {
-            if child.is_element() {
-                children.push(XNode::convert(child));
-            }
-        }
+        let children = convert_mixed_children(node, ctx, depth)?;
 
-        Self {
+        Ok(Self {
             id,
+            location,
             classes,
+            inline_style,
+            attributes: node.attributes.clone(),
+            disabled,
             style: Style::default(),
-            content,
             children,
+        })
+    }
+
+    /// State of the button when it is spawned
+    fn element_state(&self) -> ElementState {
+        ElementState {
+            disabled: self.disabled,
+            ..Default::default()
         }
     }
 
     pub fn resolve(
         &mut self,
         load_context: &mut LoadContext,
-        fonts: &HashMap<String, Handle<Font>>,
-        default_font: Option<Handle<Font>>,
-    ) {
-        self.style = Style::parse(&self.classes, fonts, default_font.clone());
-        for child in &mut self.children {
-            child.resolve(load_context, fonts, default_font.clone());
-        }
+        ctx: &StyleContext,
+        parent: Option<&Style>,
+        position: SiblingPosition,
+    ) -> Result<(), HtmlUiLoadError> {
+        self.style = parse_style(
+            &self.classes,
+            &self.inline_style,
+            &self.location,
+            load_context,
+            ctx,
+            &VariantState {
+                element: self.element_state(),
+                position,
+                ..Default::default()
+            },
+            parent,
+        )?;
+        resolve_children(&mut self.children, 0, load_context, ctx, &self.style)?;
+
+        Ok(())
     }
 
-    fn apply_to_entity(&self, commands: &mut EntityCommands) {
-        commands.insert((Button, self.style.to_node()));
+    fn apply_to_entity(&self, commands: &mut EntityCommands, ctx: &SpawnContext) {
+        commands.insert(Button);
+        if self.disabled {
+            commands.insert(InteractionDisabled);
+        }
+        HtmlNodeStyle::new(&self.style, ctx)
+            .with_inline_style(&self.inline_style)
+            .with_element_state(self.element_state())
+            .spawn(commands, &self.classes);
 
         if let Some(id) = &self.id {
             commands.insert(HtmlId(id.clone()));
         }
-
-        if let Some(content) = &self.content {
-            commands.with_child(Text::new(content));
+        if !self.attributes.is_empty() {
+            commands.insert(HtmlAttributes(self.attributes.clone()));
         }
 
         commands.with_children(|parent| {
             for child in &self.children {
                 let mut child_commands = parent.spawn_empty();
-                child.apply_to_entity(&mut child_commands);
+                child.apply_to_entity(&mut child_commands, ctx);
             }
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whitespace_runs_become_one_space() {
+        let mut whitespace = WhitespaceCollapser::default();
+        assert_eq!(whitespace.collapse("a  b\t\n c"), "a b c");
+        assert_eq!(whitespace.collapse("\u{a0}\u{a0}"), "\u{a0}\u{a0}");
+    }
+
+    #[test]
+    fn leading_whitespace_is_removed() {
+        let mut whitespace = WhitespaceCollapser::default();
+        assert_eq!(whitespace.collapse("  \n a "), "a ");
+        assert_eq!(whitespace.collapse("   "), "");
+    }
+
+    #[test]
+    fn whitespace_collapses_across_text_nodes() {
+        let mut whitespace = WhitespaceCollapser::default();
+        assert_eq!(whitespace.collapse("a "), "a ");
+        assert_eq!(whitespace.collapse(" b"), "b");
+        assert_eq!(whitespace.collapse("\n"), " ");
+        assert_eq!(whitespace.collapse(" \r\x0c"), "");
+    }
+
+    #[test]
+    fn whitespace_after_line_break_is_removed() {
+        let mut whitespace = WhitespaceCollapser::default();
+        assert_eq!(whitespace.collapse("a"), "a");
+        assert_eq!(whitespace.line_break(), "\n");
+        assert_eq!(whitespace.collapse("  b"), "b");
+    }
+}
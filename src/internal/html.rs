@@ -0,0 +1,484 @@
+//! Lenient parser for HTML documents
+//!
+//! In contrast to XML, void elements (`<br>`, `<img src="a.png">`), unquoted and boolean
+//! attributes, named character references like `&nbsp;` and missing or mismatched end tags
+//! are accepted.
+//! Self-closing syntax (`<div/>`) is honoured on every element.
+
+use crate::internal::dom::SourceLocation;
+use crate::internal::markup::RawElement;
+use crate::internal::markup::RawNode;
+
+/// Elements that never have children
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "font", "hr", "img", "include", "input", "link", "meta",
+    "source", "track", "wbr",
+];
+
+/// Elements whose content is not parsed as markup
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+/// Elements that implicitly close an open `<p>`
+const CLOSES_PARAGRAPH: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "div",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Parse an HTML document
+///
+/// This never fails, malformed markup is recovered from the same way browsers would do it
+/// in most cases.
+pub fn parse(input: &str) -> Vec<RawNode> {
+    let mut parser = Parser {
+        input,
+        pos: 0,
+        line_starts: std::iter::once(0)
+            .chain(input.match_indices('\n').map(|(i, _)| i + 1))
+            .collect(),
+        stack: vec![],
+        roots: vec![],
+    };
+    parser.parse();
+    parser.finish()
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    line_starts: Vec<usize>,
+    /// Currently open elements
+    stack: Vec<RawElement>,
+    roots: Vec<RawNode>,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(&mut self) {
+        while self.pos < self.input.len() {
+            let rest = &self.input[self.pos..];
+
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                self.pos += comment.find("-->").map(|i| i + 7).unwrap_or(rest.len());
+            } else if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                let end = cdata.find("]]>").unwrap_or(cdata.len());
+                self.push_node(RawNode::Text(cdata[..end].to_string()));
+                self.pos += (end + 12).min(rest.len());
+            } else if rest.starts_with("<!") || rest.starts_with("<?") {
+                self.pos += rest.find('>').map(|i| i + 1).unwrap_or(rest.len());
+            } else if rest.starts_with("</") {
+                self.parse_end_tag();
+            } else if rest.starts_with('<')
+                && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+            {
+                self.parse_start_tag();
+            } else {
+                // A `<` that doesn't start a tag is part of the text
+                let skip = usize::from(rest.starts_with('<'));
+                let end = rest[skip..]
+                    .find('<')
+                    .map(|i| i + skip)
+                    .unwrap_or(rest.len());
+                self.push_node(RawNode::Text(decode_entities(&rest[..end])));
+                self.pos += end;
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<RawNode> {
+        while self.close_current() {}
+        self.roots
+    }
+
+    fn location(&self, pos: usize) -> SourceLocation {
+        let line = self.line_starts.partition_point(|start| *start <= pos);
+        let line_start = self.line_starts[line - 1];
+
+        SourceLocation {
+            line: line as u32,
+            column: self.input[line_start..pos].chars().count() as u32 + 1,
+            source: None,
+        }
+    }
+
+    fn push_node(&mut self, node: RawNode) {
+        let siblings = match self.stack.last_mut() {
+            Some(parent) => &mut parent.children,
+            None => &mut self.roots,
+        };
+
+        // Adjacent text is merged into a single node
+        match (siblings.last_mut(), node) {
+            (Some(RawNode::Text(text)), RawNode::Text(next)) => text.push_str(&next),
+            (_, node) => siblings.push(node),
+        }
+    }
+
+    /// Close the innermost open element, returns false if there is none
+    fn close_current(&mut self) -> bool {
+        let Some(element) = self.stack.pop() else {
+            return false;
+        };
+        self.push_node(RawNode::Element(element));
+        true
+    }
+
+    /// Close all elements up to and including the innermost element with the given name
+    ///
+    /// Nothing is closed if no such element is open
+    fn close_until(&mut self, name: &str) {
+        if let Some(index) = self.stack.iter().rposition(|e| e.name == name) {
+            while self.stack.len() > index {
+                self.close_current();
+            }
+        }
+    }
+
+    fn parse_end_tag(&mut self) {
+        let rest = &self.input[self.pos + 2..];
+        let name_len = rest
+            .find(|c: char| c.is_whitespace() || c == '>')
+            .unwrap_or(rest.len());
+        let name = rest[..name_len].to_lowercase();
+        self.pos += 2 + rest.find('>').map(|i| i + 1).unwrap_or(rest.len());
+
+        self.close_until(&name);
+    }
+
+    fn parse_start_tag(&mut self) {
+        let location = self.location(self.pos);
+        self.pos += 1;
+
+        let name = self.take_while(|c| !c.is_whitespace() && c != '>' && c != '/');
+        let name = name.to_lowercase();
+
+        let mut attributes = Vec::new();
+        let mut self_closing = false;
+
+        loop {
+            self.take_while(char::is_whitespace);
+            let rest = &self.input[self.pos..];
+
+            if rest.is_empty() {
+                break;
+            } else if rest.starts_with("/>") {
+                self_closing = true;
+                self.pos += 2;
+                break;
+            } else if rest.starts_with('>') {
+                self.pos += 1;
+                break;
+            } else if rest.starts_with('/') {
+                self.pos += 1;
+                continue;
+            }
+
+            let key = self
+                .take_while(|c| !c.is_whitespace() && c != '=' && c != '>' && c != '/')
+                .to_lowercase();
+            self.take_while(char::is_whitespace);
+
+            let value = if self.input[self.pos..].starts_with('=') {
+                self.pos += 1;
+                self.take_while(char::is_whitespace);
+                self.parse_attribute_value()
+            } else {
+                String::new()
+            };
+
+            if !attributes.iter().any(|(k, _)| *k == key) {
+                attributes.push((key, value));
+            }
+        }
+
+        // A list item closes the previous item of the same list
+        if name == "li"
+            && let Some(index) = self.stack.iter().rposition(|e| e.name == "li")
+            && !self.stack[index..]
+                .iter()
+                .any(|e| e.name == "ul" || e.name == "ol")
+        {
+            while self.stack.len() > index {
+                self.close_current();
+            }
+        } else if CLOSES_PARAGRAPH.contains(&name.as_str())
+            && self.stack.last().is_some_and(|e| e.name == "p")
+        {
+            self.close_current();
+        }
+
+        let mut element = RawElement {
+            name,
+            attributes,
+            children: vec![],
+            location,
+        };
+
+        if self_closing || VOID_ELEMENTS.contains(&element.name.as_str()) {
+            self.push_node(RawNode::Element(element));
+        } else if RAW_TEXT_ELEMENTS.contains(&element.name.as_str()) {
+            let rest = &self.input[self.pos..];
+            let end = rest
+                .to_ascii_lowercase()
+                .find(&format!("</{}", element.name))
+                .unwrap_or(rest.len());
+            if end > 0 {
+                element
+                    .children
+                    .push(RawNode::Text(rest[..end].to_string()));
+            }
+            self.pos += end;
+            self.stack.push(element);
+        } else {
+            self.stack.push(element);
+        }
+    }
+
+    fn parse_attribute_value(&mut self) -> String {
+        let rest = &self.input[self.pos..];
+
+        if let Some(quote) = rest.chars().next().filter(|c| *c == '"' || *c == '\'') {
+            let end = rest[1..].find(quote).map(|i| i + 1).unwrap_or(rest.len());
+            let value = decode_entities(&rest[1..end]);
+            self.pos += (end + 1).min(rest.len());
+            value
+        } else {
+            let value = self.take_while(|c| !c.is_whitespace() && c != '>');
+
+            // The slash belongs to a self-closing tag and not to the value
+            match value.strip_suffix('/') {
+                Some(value) if self.input[self.pos..].starts_with('>') => {
+                    self.pos -= 1;
+                    decode_entities(value)
+                }
+                _ => decode_entities(value),
+            }
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = &self.input[self.pos..];
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+}
+
+/// Replace character references with the characters they stand for
+///
+/// Unknown references are kept as they are.
+pub fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let replacement = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 32)
+            .and_then(|end| decode_entity(&rest[1..end + 1]).map(|c| (c, end + 2)));
+
+        match replacement {
+            Some((c, len)) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(number) = entity.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+
+    Some(match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "ensp" => '\u{2002}',
+        "emsp" => '\u{2003}',
+        "thinsp" => '\u{2009}',
+        "shy" => '\u{ad}',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "deg" => '°',
+        "plusmn" => '±',
+        "times" => '×',
+        "divide" => '÷',
+        "middot" => '·',
+        "bull" => '•',
+        "hellip" => '…',
+        "ndash" => '–',
+        "mdash" => '—',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "larr" => '←',
+        "uarr" => '↑',
+        "rarr" => '→',
+        "darr" => '↓',
+        "euro" => '€',
+        "pound" => '£',
+        "yen" => '¥',
+        "cent" => '¢',
+        "sect" => '§',
+        "para" => '¶',
+        "frac12" => '½',
+        "frac14" => '¼',
+        "frac34" => '¾',
+        "hearts" => '♥',
+        "star" => '☆',
+        "check" => '✓',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::testing::render;
+
+    fn assert_parses(input: &str, expected: &str) {
+        assert_eq!(render(&parse(input)), expected, "{input}");
+    }
+
+    #[test]
+    fn implicit_end_tags() {
+        assert_parses("<p>a<div>b</div>", "<p>a</p><div>b</div>");
+        assert_parses("<p>a<p>b", "<p>a</p><p>b</p>");
+        assert_parses("<p>a<span>b</span>", "<p>a<span>b</span></p>");
+        assert_parses("<div><span>a", "<div><span>a</span></div>");
+    }
+
+    #[test]
+    fn list_items_close_the_previous_item() {
+        assert_parses("<ul><li>a<li>b</ul>", "<ul><li>a</li><li>b</li></ul>");
+        assert_parses(
+            "<ul><li>a<ul><li>b</ul><li>c</ul>",
+            "<ul><li>a<ul><li>b</li></ul></li><li>c</li></ul>",
+        );
+    }
+
+    #[test]
+    fn stray_end_tags_are_ignored() {
+        assert_parses("<div>a</span>b</div>", "<div>ab</div>");
+        assert_parses("</p><p>a</p>", "<p>a</p>");
+    }
+
+    #[test]
+    fn void_and_self_closing_elements() {
+        assert_parses(
+            "<p>a<br>b<img src=x.png>c</p>",
+            "<p>a<br></br>b<img src=\"x.png\"></img>c</p>",
+        );
+        assert_parses("<div/><span>a</span>", "<div></div><span>a</span>");
+        assert_parses("<div class=a/>b", "<div class=\"a\"></div>b");
+        assert_parses(
+            "<include src='a.html'>b",
+            "<include src=\"a.html\"></include>b",
+        );
+    }
+
+    #[test]
+    fn attributes() {
+        assert_parses(
+            "<button disabled class=primary ID=\"a\" class=\"b\" data-x='1 2'></button>",
+            "<button disabled=\"\" class=\"primary\" id=\"a\" data-x=\"1 2\"></button>",
+        );
+        assert_parses("<DIV Title = 'x'></div>", "<div title=\"x\"></div>");
+    }
+
+    #[test]
+    fn raw_text_elements() {
+        assert_parses(
+            "<style>.a > .b { color: red }</style><p>x</p>",
+            "<style>.a > .b { color: red }</style><p>x</p>",
+        );
+        assert_parses(
+            "<script>if (a<b) {}</SCRIPT>",
+            "<script>if (a<b) {}</script>",
+        );
+        assert_parses("<style>a &amp; b</style>", "<style>a &amp; b</style>");
+        assert_parses("<style></style>", "<style></style>");
+    }
+
+    #[test]
+    fn character_references() {
+        assert_parses(
+            "<p>&amp;&lt;&#65;&#x42;&nbsp;&unknown; AT&T</p>",
+            "<p>&<AB\u{a0}&unknown; AT&T</p>",
+        );
+        assert_parses(
+            "<div title=\"a &quot;b&quot;\"></div>",
+            "<div title=\"a \"b\"\"></div>",
+        );
+        assert_eq!(decode_entities("&#x1F600;"), "\u{1F600}");
+        assert_eq!(decode_entities("&#xD800;"), "&#xD800;");
+        assert_eq!(decode_entities("a & b;"), "a & b;");
+    }
+
+    #[test]
+    fn markup_that_is_not_an_element() {
+        assert_parses("<!DOCTYPE html><!-- <div> --><p>a</p>", "<p>a</p>");
+        assert_parses("<?xml version=\"1.0\"?><p>a</p>", "<p>a</p>");
+        assert_parses("<p><![CDATA[a&b]]></p>", "<p>a&b</p>");
+        assert_parses("<p>a < b</p>", "<p>a < b</p>");
+        assert_parses("<p>a<!-- unclosed", "<p>a</p>");
+    }
+
+    #[test]
+    fn locations() {
+        let nodes = parse("<div>\n  <p>é <span>x</span></p>\n</div>");
+        let [RawNode::Element(div)] = &nodes[..] else {
+            panic!("expected a single element: {nodes:?}");
+        };
+        let p = div.elements().next().unwrap();
+        let span = p.elements().next().unwrap();
+
+        assert_eq!((div.location.line, div.location.column), (1, 1));
+        assert_eq!((p.location.line, p.location.column), (2, 3));
+        assert_eq!((span.location.line, span.location.column), (2, 8));
+    }
+}
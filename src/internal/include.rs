@@ -0,0 +1,564 @@
+//! Partials inlined by `<include src="ui/partials/topbar.html"/>` and components defined by
+//! `<component name="menu-button" src="ui/button.html"/>`
+
+use bevy::asset::AssetPath;
+use bevy::asset::LoadContext;
+use bevy::platform::collections::HashMap;
+
+use crate::assets::HtmlUiLoadError;
+use crate::assets::ParseMode;
+use crate::internal::dom::BUILT_IN_TAGS;
+use crate::internal::markup::RawElement;
+use crate::internal::markup::RawNode;
+use crate::internal::markup::parse_document;
+
+/// Partials and component templates of a document
+#[derive(Debug, Default)]
+pub(crate) struct Partials {
+    /// Parsed files by the path of their `src` attribute
+    pub documents: HashMap<String, Vec<RawNode>>,
+    /// Path of the template of each component by its tag name
+    pub components: HashMap<String, String>,
+}
+
+/// Collect the components registered by `<component>` elements in the head
+pub(crate) fn component_definitions(
+    nodes: &[RawNode],
+    path: &AssetPath<'static>,
+) -> Result<HashMap<String, String>, HtmlUiLoadError> {
+    let mut components = HashMap::new();
+
+    let heads = nodes
+        .iter()
+        .filter_map(element)
+        .flat_map(|node| match node.name.as_str() {
+            "html" => node.elements().collect(),
+            _ => vec![node],
+        })
+        .filter(|node| node.name == "head");
+    for component in heads
+        .flat_map(RawElement::elements)
+        .filter(|node| node.name == "component")
+    {
+        let missing = |attribute| HtmlUiLoadError::MissingAttribute {
+            path: path.clone(),
+            tag: "component",
+            attribute,
+            line: component.location.line,
+            column: component.location.column,
+        };
+        let name = component.attribute("name").ok_or_else(|| missing("name"))?;
+        let src = component.attribute("src").ok_or_else(|| missing("src"))?;
+        let name = name.to_lowercase();
+        if BUILT_IN_TAGS.contains(&name.as_str()) {
+            return Err(HtmlUiLoadError::ReservedComponentName {
+                path: path.clone(),
+                name,
+                line: component.location.line,
+                column: component.location.column,
+            });
+        }
+        components.insert(name, src.to_string());
+    }
+
+    Ok(components)
+}
+
+/// Read every partial that is included by the nodes or by other partials, as well as the
+/// templates of the components
+///
+/// Reading them makes them dependencies of the document, so editing a partial reloads
+/// every document including it.
+pub(crate) async fn read_partials(
+    nodes: &[RawNode],
+    components: HashMap<String, String>,
+    load_context: &mut LoadContext<'_>,
+    parse_mode: ParseMode,
+) -> Result<Partials, HtmlUiLoadError> {
+    let mut documents = HashMap::new();
+    let mut pending = components.values().cloned().collect::<Vec<_>>();
+    collect_includes(nodes, load_context.path(), &mut pending)?;
+
+    while let Some(src) = pending.pop() {
+        if documents.contains_key(&src) {
+            continue;
+        }
+
+        let path = AssetPath::from(src.clone());
+        let bytes = load_context.read_asset_bytes(path.clone()).await?;
+        let text = String::from_utf8(bytes)?;
+        let nodes = parse_document(&text, &path, parse_mode)?;
+        collect_includes(&nodes, &path, &mut pending)?;
+        documents.insert(src, nodes);
+    }
+
+    Ok(Partials {
+        documents,
+        components,
+    })
+}
+
+/// Collect the `src` attributes of all includes of a file
+///
+/// Partials are read before props are substituted, so the `src` of an include in a component
+/// template can't use them.
+fn collect_includes(
+    nodes: &[RawNode],
+    path: &AssetPath,
+    includes: &mut Vec<String>,
+) -> Result<(), HtmlUiLoadError> {
+    for node in nodes {
+        let RawNode::Element(element) = node else {
+            continue;
+        };
+        match element.attribute("src") {
+            Some(src) if element.name == "include" && src.contains("{{") => {
+                return Err(HtmlUiLoadError::IncludeWithProps {
+                    path: path.clone_owned(),
+                    src: src.to_string(),
+                    line: element.location.line,
+                    column: element.location.column,
+                });
+            }
+            Some(src) if element.name == "include" => includes.push(src.to_string()),
+            _ => collect_includes(&element.children, path, includes)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Replace the includes and components of the nodes by the nodes of their files
+///
+/// `stack` contains the document and the partials that are currently expanded, the last one
+/// contains the nodes. Including one of them again is a cycle.
+pub(crate) fn expand_includes(
+    nodes: Vec<RawNode>,
+    partials: &Partials,
+    stack: &mut Vec<String>,
+) -> Result<Vec<RawNode>, HtmlUiLoadError> {
+    let mut expanded = Vec::with_capacity(nodes.len());
+
+    for node in nodes {
+        let RawNode::Element(mut element) = node else {
+            expanded.push(node);
+            continue;
+        };
+
+        let location = element.location.clone();
+        let path = AssetPath::from(stack.last().cloned().unwrap_or_default());
+        let src = match partials.components.get(&element.name) {
+            Some(src) => src.clone(),
+            None if element.name == "include" => match element.attribute("src") {
+                Some(src) => src.to_string(),
+                None => {
+                    return Err(HtmlUiLoadError::MissingAttribute {
+                        path,
+                        tag: "include",
+                        attribute: "src",
+                        line: location.line,
+                        column: location.column,
+                    });
+                }
+            },
+            None => {
+                element.children = expand_includes(element.children, partials, stack)?;
+                expanded.push(RawNode::Element(element));
+                continue;
+            }
+        };
+        if stack.contains(&src) {
+            stack.push(src);
+            return Err(HtmlUiLoadError::IncludeCycle {
+                path,
+                cycle: stack.join(" -> "),
+                line: location.line,
+                column: location.column,
+            });
+        }
+
+        // Every partial was read before
+        let mut partial = partials.documents.get(&src).cloned().unwrap_or_default();
+        set_source(&mut partial, &AssetPath::from(src.clone()));
+        let is_component = element.name != "include";
+        if is_component {
+            substitute_props(&mut partial, &element.attributes);
+        }
+
+        stack.push(src);
+        let mut nodes = expand_includes(partial, partials, stack)?;
+        stack.pop();
+
+        if is_component {
+            // Children are part of the document using the component, not of the template
+            let children = expand_includes(element.children, partials, stack)?;
+            nodes = fill_slots(nodes, &children);
+            pass_through_attributes(&mut nodes, &element.attributes);
+        }
+        expanded.extend(nodes);
+    }
+
+    Ok(expanded)
+}
+
+/// Record the file that the elements were read from, so errors report it instead of the
+/// document
+fn set_source(nodes: &mut [RawNode], source: &AssetPath<'static>) {
+    for node in nodes {
+        if let RawNode::Element(element) = node {
+            element.location.source = Some(source.clone());
+            set_source(&mut element.children, source);
+        }
+    }
+}
+
+/// Replace `{{ name }}` in texts and attributes by the value of the prop, props that aren't
+/// set are empty
+fn substitute_props(nodes: &mut [RawNode], props: &[(String, String)]) {
+    for node in nodes {
+        match node {
+            RawNode::Text(text) => *text = substitute(text, props),
+            RawNode::Element(element) => {
+                for (_, value) in &mut element.attributes {
+                    *value = substitute(value, props);
+                }
+                substitute_props(&mut element.children, props);
+            }
+        }
+    }
+}
+
+fn substitute(text: &str, props: &[(String, String)]) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let name = rest[start + 2..start + end].trim();
+        if let Some((_, value)) = props.iter().find(|(prop, _)| prop == name) {
+            result.push_str(value);
+        }
+        rest = &rest[start + end + 2..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Replace the `<slot>` elements by the children of the component
+///
+/// Without children, the content of the slot is kept as a fallback.
+fn fill_slots(nodes: Vec<RawNode>, children: &[RawNode]) -> Vec<RawNode> {
+    let has_children = children.iter().any(|child| match child {
+        RawNode::Element(_) => true,
+        RawNode::Text(text) => !text.trim().is_empty(),
+    });
+
+    let mut filled = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        match node {
+            RawNode::Element(element) if element.name == "slot" => {
+                if has_children {
+                    filled.extend(children.iter().cloned());
+                } else {
+                    filled.extend(element.children);
+                }
+            }
+            RawNode::Element(mut element) => {
+                element.children = fill_slots(element.children, children);
+                filled.push(RawNode::Element(element));
+            }
+            RawNode::Text(_) => filled.push(node),
+        }
+    }
+
+    filled
+}
+
+/// Apply `id`, `class` and `style` of the component to the first element of its template
+///
+/// Classes and styles are added to the ones of the template, the id replaces it.
+fn pass_through_attributes(nodes: &mut [RawNode], attributes: &[(String, String)]) {
+    let Some(root) = nodes.iter_mut().find_map(|node| match node {
+        RawNode::Element(element) => Some(element),
+        RawNode::Text(_) => None,
+    }) else {
+        return;
+    };
+
+    for (name, value) in attributes {
+        let separator = match name.as_str() {
+            "id" => None,
+            "class" => Some(" "),
+            "style" => Some(";"),
+            _ => continue,
+        };
+        match root.attributes.iter_mut().find(|(key, _)| key == name) {
+            Some((_, existing)) => match separator {
+                Some(separator) => *existing = format!("{existing}{separator}{value}"),
+                None => existing.clone_from(value),
+            },
+            None => root.attributes.push((name.clone(), value.clone())),
+        }
+    }
+}
+
+fn element(node: &RawNode) -> Option<&RawElement> {
+    match node {
+        RawNode::Element(element) => Some(element),
+        RawNode::Text(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::html;
+    use crate::internal::testing::render;
+
+    fn partials(documents: &[(&str, &str)], components: &[(&str, &str)]) -> Partials {
+        Partials {
+            documents: documents
+                .iter()
+                .map(|(src, html)| (src.to_string(), html::parse(html)))
+                .collect(),
+            components: components
+                .iter()
+                .map(|(name, src)| (name.to_string(), src.to_string()))
+                .collect(),
+        }
+    }
+
+    fn expand(html: &str, partials: &Partials) -> Result<String, HtmlUiLoadError> {
+        let nodes = expand_includes(html::parse(html), partials, &mut vec!["doc.html".into()])?;
+        Ok(render(&nodes))
+    }
+
+    #[test]
+    fn includes_are_inlined() {
+        let partials = partials(
+            &[
+                ("top.html", "<nav><include src=\"logo.html\"/></nav>"),
+                ("logo.html", "<img src=\"logo.png\"/>"),
+            ],
+            &[],
+        );
+        assert_eq!(
+            expand("<div><include src=\"top.html\"/><p>a</p></div>", &partials).unwrap(),
+            "<div><nav><img src=\"logo.png\"></img></nav><p>a</p></div>"
+        );
+        // Including a partial twice isn't a cycle
+        assert_eq!(
+            expand(
+                "<include src=\"logo.html\"/><include src=\"logo.html\"/>",
+                &partials
+            )
+            .unwrap(),
+            "<img src=\"logo.png\"></img><img src=\"logo.png\"></img>"
+        );
+    }
+
+    #[test]
+    fn collect_nested_includes() {
+        let mut includes = vec![];
+        collect_includes(
+            &html::parse(
+                "<div><include src=\"a.html\"/><p><include src=\"b.html\"/></p></div><include/>",
+            ),
+            &AssetPath::from("doc.html"),
+            &mut includes,
+        )
+        .unwrap();
+        assert_eq!(includes, ["a.html", "b.html"]);
+    }
+
+    #[test]
+    fn include_src_without_props() {
+        let error = collect_includes(
+            &html::parse("<div>\n  <include src=\"icons/{{ icon }}.html\"/></div>"),
+            &AssetPath::from("button.html"),
+            &mut vec![],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            HtmlUiLoadError::IncludeWithProps { path, src, line: 2, column: 3 }
+                if path == AssetPath::from("button.html") && src == "icons/{{ icon }}.html"
+        ));
+    }
+
+    #[test]
+    fn include_without_src() {
+        let error = expand("<div>\n  <include/></div>", &Partials::default()).unwrap_err();
+        assert!(matches!(
+            error,
+            HtmlUiLoadError::MissingAttribute {
+                tag: "include",
+                attribute: "src",
+                line: 2,
+                column: 3,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn include_cycles() {
+        let partials = partials(
+            &[
+                ("a.html", "<div><include src=\"b.html\"/></div>"),
+                ("b.html", "<include src=\"a.html\"/>"),
+            ],
+            &[],
+        );
+        let error = expand("<include src=\"a.html\"/>", &partials).unwrap_err();
+        let HtmlUiLoadError::IncludeCycle { path, cycle, .. } = error else {
+            panic!("Expected an include cycle, got {error}");
+        };
+        assert_eq!(path, AssetPath::from("b.html"));
+        assert_eq!(cycle, "doc.html -> a.html -> b.html -> a.html");
+
+        let error = expand("<include src=\"doc.html\"/>", &Partials::default()).unwrap_err();
+        assert!(matches!(
+            error,
+            HtmlUiLoadError::IncludeCycle { cycle, .. } if cycle == "doc.html -> doc.html"
+        ));
+    }
+
+    #[test]
+    fn component_definitions_in_the_head() {
+        let path = AssetPath::from("doc.html");
+        let nodes = html::parse(
+            "<html><head><component name=\"Menu-Button\" src=\"button.html\"/></head></html>\
+             <head><component name=\"card\" src=\"card.html\"/></head>\
+             <body><component name=\"ignored\" src=\"x.html\"/></body>",
+        );
+        let components = component_definitions(&nodes, &path).unwrap();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components["menu-button"], "button.html");
+        assert_eq!(components["card"], "card.html");
+
+        let nodes = html::parse("<head><component name=\"card\"/></head>");
+        assert!(matches!(
+            component_definitions(&nodes, &path),
+            Err(HtmlUiLoadError::MissingAttribute {
+                tag: "component",
+                attribute: "src",
+                ..
+            })
+        ));
+        let nodes = html::parse("<head><component src=\"card.html\"/></head>");
+        assert!(matches!(
+            component_definitions(&nodes, &path),
+            Err(HtmlUiLoadError::MissingAttribute {
+                attribute: "name",
+                ..
+            })
+        ));
+
+        for name in ["div", "Button", "slot", "include"] {
+            let nodes = html::parse(&format!(
+                "<head><component name=\"{name}\" src=\"card.html\"/></head>"
+            ));
+            assert!(
+                matches!(
+                    component_definitions(&nodes, &path),
+                    Err(HtmlUiLoadError::ReservedComponentName { name: reserved, .. })
+                        if reserved == name.to_lowercase()
+                ),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn props_in_text() {
+        let props = [
+            ("label".to_string(), "Save".to_string()),
+            ("icon".to_string(), "disk".to_string()),
+        ];
+        assert_eq!(substitute("{{label}}", &props), "Save");
+        assert_eq!(
+            substitute("a {{ label }} b {{icon}}{{ label }}", &props),
+            "a Save b diskSave"
+        );
+        // Props that aren't set are empty
+        assert_eq!(substitute("[{{ missing }}]", &props), "[]");
+        assert_eq!(substitute("{{ label", &props), "{{ label");
+        assert_eq!(substitute("{ label }}", &props), "{ label }}");
+        assert_eq!(substitute("", &props), "");
+    }
+
+    #[test]
+    fn slots_are_filled_with_children() {
+        let template = html::parse("<div><slot>fallback</slot><p><slot/></p></div>");
+        assert_eq!(
+            render(&fill_slots(template.clone(), &html::parse("<b>a</b>b"))),
+            "<div><b>a</b>b<p><b>a</b>b</p></div>"
+        );
+        // Without children, the content of the slot is the fallback
+        assert_eq!(
+            render(&fill_slots(template.clone(), &html::parse(" \n "))),
+            "<div>fallback<p></p></div>"
+        );
+        assert_eq!(
+            render(&fill_slots(template, &[])),
+            "<div>fallback<p></p></div>"
+        );
+    }
+
+    #[test]
+    fn components_are_expanded() {
+        let partials = partials(
+            &[(
+                "button.html",
+                "<button class=\"btn\" style=\"width: 10px\" id=\"template\">\
+                 {{ label }}<slot>default</slot></button>",
+            )],
+            &[("menu-button", "button.html")],
+        );
+        assert_eq!(
+            expand(
+                "<menu-button label=\"Save\" class=\"p-4\" style=\"height: 4px\" id=\"save\">\
+                 <b>now</b></menu-button>",
+                &partials
+            )
+            .unwrap(),
+            "<button class=\"btn p-4\" style=\"width: 10px;height: 4px\" id=\"save\">\
+             Save<b>now</b></button>"
+        );
+        assert_eq!(
+            expand("<menu-button/>", &partials).unwrap(),
+            "<button class=\"btn\" style=\"width: 10px\" id=\"template\">default</button>"
+        );
+    }
+
+    #[test]
+    fn props_are_not_substituted_in_children() {
+        let partials = partials(
+            &[("card.html", "<div>{{ title }}<slot/></div>")],
+            &[("card", "card.html")],
+        );
+        assert_eq!(
+            expand("<card title=\"A\">{{ title }}</card>", &partials).unwrap(),
+            "<div>A{{ title }}</div>"
+        );
+    }
+
+    #[test]
+    fn component_cycles() {
+        let partials = partials(
+            &[("tree.html", "<div><tree/></div>")],
+            &[("tree", "tree.html")],
+        );
+        let error = expand("<tree/>", &partials).unwrap_err();
+        assert!(matches!(
+            error,
+            HtmlUiLoadError::IncludeCycle { cycle, .. }
+                if cycle == "doc.html -> tree.html -> tree.html"
+        ));
+    }
+}
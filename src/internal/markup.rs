@@ -0,0 +1,119 @@
+use bevy::asset::AssetPath;
+use roxmltree::Document;
+
+use crate::assets::HtmlUiLoadError;
+use crate::assets::ParseMode;
+use crate::internal::dom::SourceLocation;
+use crate::internal::html;
+
+/// Parse a document with the parser selected by the mode
+///
+/// In [`ParseMode::Auto`], `.xml` files are parsed as XML, everything else as HTML.
+pub fn parse_document(
+    text: &str,
+    path: &AssetPath,
+    mode: ParseMode,
+) -> Result<Vec<RawNode>, HtmlUiLoadError> {
+    let is_xml = path
+        .path()
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"));
+
+    let parse_xml = || {
+        Document::parse(text)
+            .map(|document| RawNode::from_xml(&document))
+            .map_err(|error| HtmlUiLoadError::XMLError {
+                path: path.clone_owned(),
+                error: Box::new(error),
+            })
+    };
+
+    match mode {
+        ParseMode::Xml => parse_xml(),
+        ParseMode::Auto if is_xml => parse_xml(),
+        ParseMode::Html | ParseMode::Auto => Ok(html::parse(text)),
+    }
+}
+
+/// Node of the parsed markup, independent of the parser that produced it
+#[derive(Debug, Clone)]
+pub enum RawNode {
+    Element(RawElement),
+    Text(String),
+}
+
+/// Element of the parsed markup
+#[derive(Debug, Clone)]
+pub struct RawElement {
+    /// Lowercase tag name
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<RawNode>,
+    pub location: SourceLocation,
+}
+
+impl RawNode {
+    /// Build the tree from a document parsed by roxmltree
+    pub fn from_xml(document: &roxmltree::Document) -> Vec<Self> {
+        document
+            .root()
+            .children()
+            .filter_map(Self::from_xml_node)
+            .collect()
+    }
+
+    fn from_xml_node(node: roxmltree::Node) -> Option<Self> {
+        if node.is_text() {
+            return node.text().map(|text| Self::Text(text.to_string()));
+        }
+
+        if !node.is_element() {
+            return None;
+        }
+
+        let pos = node.document().text_pos_at(node.range().start);
+
+        Some(Self::Element(RawElement {
+            name: node.tag_name().name().to_lowercase(),
+            attributes: node
+                .attributes()
+                .map(|attr| (attr.name().to_string(), attr.value().to_string()))
+                .collect(),
+            children: node.children().filter_map(Self::from_xml_node).collect(),
+            location: SourceLocation {
+                line: pos.row,
+                column: pos.col,
+                source: None,
+            },
+        }))
+    }
+}
+
+impl RawElement {
+    /// Value of the attribute with the given name
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Text content of the direct children, e.g. of a `<style>` element
+    pub fn text(&self) -> String {
+        self.children
+            .iter()
+            .filter_map(|child| match child {
+                RawNode::Text(text) => Some(text.as_str()),
+                RawNode::Element(_) => None,
+            })
+            .collect()
+    }
+
+    /// Child elements, skipping text
+    pub fn elements(&self) -> impl Iterator<Item = &RawElement> {
+        self.children.iter().filter_map(|child| match child {
+            RawNode::Element(element) => Some(element),
+            RawNode::Text(_) => None,
+        })
+    }
+}
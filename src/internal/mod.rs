@@ -1,2 +1,10 @@
+pub mod color;
+pub mod css;
 pub mod dom;
+pub mod html;
+pub mod include;
+pub mod markup;
+pub mod stylesheet;
 pub mod tailwind;
+#[cfg(test)]
+pub mod testing;
@@ -0,0 +1,346 @@
+//! Stylesheets of `<style>` elements and linked files
+//!
+//! Only rules with simple class selectors are supported, which define component classes by
+//! applying utilities, e.g. `.btn { @apply border border-white p-[20px]; }`.
+
+use std::borrow::Cow;
+
+use bevy::platform::collections::HashMap;
+use thiserror::Error;
+
+use crate::internal::tailwind::UnsupportedClasses;
+use crate::internal::tailwind::handle_unsupported;
+use crate::internal::tailwind::split_variants;
+
+/// Pseudo-classes of selectors and the variants they correspond to
+const PSEUDO_CLASSES: &[(&str, &str)] = &[
+    ("hover", "hover"),
+    ("focus", "focus"),
+    ("active", "active"),
+    ("disabled", "disabled"),
+    ("checked", "checked"),
+    ("first-child", "first"),
+    ("last-child", "last"),
+    ("only-child", "only"),
+];
+
+/// Component classes defined by the stylesheets of a document
+#[derive(Debug, Clone, Default)]
+pub struct Stylesheet {
+    /// Utilities of each component class, pseudo-classes of the selector are prepended as
+    /// variants
+    pub components: HashMap<String, Vec<String>>,
+}
+
+/// Errors that can occur while parsing a stylesheet
+#[derive(Error, Debug)]
+pub enum StylesheetError {
+    #[error("Unclosed block of {0}")]
+    UnclosedBlock(String),
+    #[error("Unexpected closing brace")]
+    UnexpectedClosingBrace,
+    #[error("Unclosed comment")]
+    UnclosedComment,
+    #[error("Unsupported selector: {0}")]
+    UnsupportedSelector(String),
+    #[error("Unsupported declaration: {0}")]
+    UnsupportedDeclaration(String),
+}
+
+impl Stylesheet {
+    /// Add the rules of a stylesheet
+    ///
+    /// Later rules of the same class add to the earlier ones. Unsupported selectors and
+    /// declarations are handled like unsupported classes.
+    pub fn parse(
+        &mut self,
+        css: &str,
+        unsupported: UnsupportedClasses,
+    ) -> Result<(), StylesheetError> {
+        let css = remove_comments(css)?;
+        let mut rest = css.as_str();
+
+        while let Some(open) = rest.find(['{', '}']) {
+            let selector = rest[..open].trim();
+            if rest[open..].starts_with('}') {
+                return Err(StylesheetError::UnexpectedClosingBrace);
+            }
+
+            // Find the matching brace, nested blocks like `@media` are skipped as a whole
+            let mut depth = 0usize;
+            let mut nested = false;
+            let mut close = None;
+            for (index, c) in rest[open..].char_indices() {
+                match c {
+                    '{' => {
+                        depth += 1;
+                        nested |= depth > 1;
+                    }
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            close = Some(open + index);
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let Some(close) = close else {
+                return Err(StylesheetError::UnclosedBlock(selector.to_string()));
+            };
+            let body = &rest[open + 1..close];
+            rest = &rest[close + 1..];
+
+            match selector_variants(selector) {
+                Some(selectors) if !nested => self.add_rule(&selectors, body, unsupported)?,
+                _ => handle_unsupported(
+                    StylesheetError::UnsupportedSelector(selector.to_string()),
+                    unsupported,
+                )?,
+            }
+        }
+
+        if !rest.trim().is_empty() {
+            handle_unsupported(
+                StylesheetError::UnsupportedDeclaration(rest.trim().to_string()),
+                unsupported,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Add the `@apply` declarations of a rule to each of its classes
+    fn add_rule(
+        &mut self,
+        selectors: &[(&str, String)],
+        body: &str,
+        unsupported: UnsupportedClasses,
+    ) -> Result<(), StylesheetError> {
+        let mut classes = vec![];
+        for declaration in body.split(';').map(str::trim) {
+            if declaration.is_empty() {
+                continue;
+            }
+            match declaration.strip_prefix("@apply") {
+                Some(utilities) if utilities.starts_with(char::is_whitespace) => {
+                    classes.extend(utilities.split_ascii_whitespace());
+                }
+                _ => handle_unsupported(
+                    StylesheetError::UnsupportedDeclaration(declaration.to_string()),
+                    unsupported,
+                )?,
+            }
+        }
+
+        for (name, prefix) in selectors {
+            // Component classes defined before are expanded
+            let utilities = classes
+                .iter()
+                .flat_map(|class| self.expand_class(class))
+                .map(|class| format!("{prefix}{class}"))
+                .collect::<Vec<_>>();
+            self.components
+                .entry(name.to_string())
+                .or_default()
+                .extend(utilities);
+        }
+
+        Ok(())
+    }
+
+    /// Replace component classes by their utilities, keeping the variants of the class
+    ///
+    /// `hover:btn` becomes `hover:border hover:p-4` if `btn` applies `border p-4`.
+    pub fn expand<'a>(&'a self, classes: &'a str) -> Vec<Cow<'a, str>> {
+        classes
+            .split_ascii_whitespace()
+            .flat_map(|class| self.expand_class(class))
+            .collect()
+    }
+
+    fn expand_class<'a>(&'a self, class: &'a str) -> Vec<Cow<'a, str>> {
+        let (_, utility) = split_variants(class);
+        match self.components.get(utility) {
+            Some(utilities) => {
+                let prefix = &class[..class.len() - utility.len()];
+                utilities
+                    .iter()
+                    .map(|utility| Cow::Owned(format!("{prefix}{utility}")))
+                    .collect()
+            }
+            None => vec![Cow::Borrowed(class)],
+        }
+    }
+}
+
+/// Class names and variant prefixes of a list of selectors like `.btn, .link:hover`
+///
+/// `None` if any of the selectors isn't a single class with pseudo-classes.
+fn selector_variants(selectors: &str) -> Option<Vec<(&str, String)>> {
+    selectors
+        .split(',')
+        .map(|selector| {
+            let mut parts = selector.trim().strip_prefix('.')?.split(':');
+            let name = parts.next()?;
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return None;
+            }
+
+            let mut prefix = String::new();
+            for pseudo in parts {
+                let (_, variant) = PSEUDO_CLASSES.iter().find(|(p, _)| *p == pseudo)?;
+                prefix.push_str(variant);
+                prefix.push(':');
+            }
+
+            Some((name, prefix))
+        })
+        .collect()
+}
+
+/// Remove `/* ... */` comments
+fn remove_comments(css: &str) -> Result<String, StylesheetError> {
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        result.push_str(&rest[..start]);
+        let end = rest[start + 2..]
+            .find("*/")
+            .ok_or(StylesheetError::UnclosedComment)?;
+        rest = &rest[start + 2 + end + 2..];
+    }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(css: &str) -> Result<Stylesheet, StylesheetError> {
+        let mut stylesheet = Stylesheet::default();
+        stylesheet.parse(css, UnsupportedClasses::Error)?;
+        Ok(stylesheet)
+    }
+
+    fn expand(stylesheet: &Stylesheet, classes: &str) -> String {
+        stylesheet.expand(classes).join(" ")
+    }
+
+    #[test]
+    fn component_classes() {
+        let stylesheet = parse(
+            "/* Buttons */ .btn { @apply border p-4; }\n.card{@apply\trounded ;; @apply bg-white}",
+        )
+        .unwrap();
+        assert_eq!(stylesheet.components["btn"], ["border", "p-4"]);
+        assert_eq!(stylesheet.components["card"], ["rounded", "bg-white"]);
+        assert_eq!(stylesheet.components.len(), 2);
+    }
+
+    #[test]
+    fn selector_lists_and_pseudo_classes() {
+        let stylesheet =
+            parse(".btn, .link:hover { @apply underline; } .btn:first-child:focus { @apply p-2 }")
+                .unwrap();
+        assert_eq!(
+            stylesheet.components["btn"],
+            ["underline", "first:focus:p-2"]
+        );
+        assert_eq!(stylesheet.components["link"], ["hover:underline"]);
+    }
+
+    #[test]
+    fn later_rules_and_stylesheets_add_to_a_class() {
+        let mut stylesheet = parse(".btn { @apply p-4 }").unwrap();
+        stylesheet
+            .parse(".btn { @apply border }", UnsupportedClasses::Error)
+            .unwrap();
+        assert_eq!(stylesheet.components["btn"], ["p-4", "border"]);
+    }
+
+    #[test]
+    fn expand_components_with_variants() {
+        let stylesheet =
+            parse(".btn { @apply border hover:p-4 } .primary { @apply btn bg-blue-500 }").unwrap();
+        // Components defined earlier are expanded when they are applied
+        assert_eq!(
+            stylesheet.components["primary"],
+            ["border", "hover:p-4", "bg-blue-500"]
+        );
+        assert_eq!(
+            expand(&stylesheet, " w-full  focus:btn "),
+            "w-full focus:border focus:hover:p-4"
+        );
+        assert_eq!(expand(&stylesheet, "btn-large"), "btn-large");
+        assert_eq!(expand(&stylesheet, ""), "");
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert!(matches!(
+            parse(".btn { @apply p-4"),
+            Err(StylesheetError::UnclosedBlock(selector)) if selector == ".btn"
+        ));
+        assert!(matches!(
+            parse(".btn { @apply p-4 } }"),
+            Err(StylesheetError::UnexpectedClosingBrace)
+        ));
+        assert!(matches!(
+            parse(".btn { @apply p-4 } /* comment"),
+            Err(StylesheetError::UnclosedComment)
+        ));
+    }
+
+    #[test]
+    fn unsupported_rules() {
+        assert!(matches!(
+            parse("div { @apply p-4 }"),
+            Err(StylesheetError::UnsupportedSelector(selector)) if selector == "div"
+        ));
+        assert!(matches!(
+            parse(".btn:nth-child(2) { @apply p-4 }"),
+            Err(StylesheetError::UnsupportedSelector(_))
+        ));
+        assert!(matches!(
+            parse("@media (min-width: 640px) { .btn { @apply p-4 } }"),
+            Err(StylesheetError::UnsupportedSelector(_))
+        ));
+        assert!(matches!(
+            parse(".btn { padding: 4px }"),
+            Err(StylesheetError::UnsupportedDeclaration(declaration))
+                if declaration == "padding: 4px"
+        ));
+        assert!(matches!(
+            parse(".btn { @applyp-4 }"),
+            Err(StylesheetError::UnsupportedDeclaration(_))
+        ));
+        assert!(matches!(
+            parse(".btn { @apply p-4 } trailing"),
+            Err(StylesheetError::UnsupportedDeclaration(rest)) if rest == "trailing"
+        ));
+    }
+
+    #[test]
+    fn skip_unsupported_rules() {
+        for unsupported in [UnsupportedClasses::Warn, UnsupportedClasses::Ignore] {
+            let mut stylesheet = Stylesheet::default();
+            stylesheet
+                .parse(
+                    "div { @apply p-4 } @media print { .a { @apply p-1 } } \
+                     .btn { color: red; @apply border }",
+                    unsupported,
+                )
+                .unwrap();
+            assert_eq!(stylesheet.components.len(), 1);
+            assert_eq!(stylesheet.components["btn"], ["border"]);
+        }
+    }
+}
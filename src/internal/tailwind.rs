@@ -1,24 +1,27 @@
 use std::sync::LazyLock;
 
-use bevy::asset::Handle;
 use bevy::color::Color;
 use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
 use bevy::text::FontSmoothing;
+use bevy::text::FontWeight;
 use bevy::text::LineBreak;
 use bevy::text::LineHeight;
 use bevy::text::TextColor;
 use bevy::text::TextFont;
 use log::warn;
+use regex::Captures;
 use regex::Regex;
+use thiserror::Error;
+
+use crate::internal::color::parse_css_color;
+use crate::internal::color::tailwind_color;
+use crate::internal::dom::FontFamily;
+use crate::internal::stylesheet::Stylesheet;
+use crate::theme::HtmlColorScheme;
+use crate::theme::HtmlTheme;
 
 pub struct TailwindRegex {
-    pub width: Regex,
-    pub min_width: Regex,
-    pub max_width: Regex,
-    pub height: Regex,
-    pub min_height: Regex,
-    pub max_height: Regex,
     pub border: Regex,
     pub border_x: Regex,
     pub border_y: Regex,
@@ -26,33 +29,9 @@ pub struct TailwindRegex {
     pub border_r: Regex,
     pub border_t: Regex,
     pub border_b: Regex,
-    pub border_color: Regex,
-    pub background_color: Regex,
-    pub padding: Regex,
-    pub padding_x: Regex,
-    pub padding_y: Regex,
-    pub padding_l: Regex,
-    pub padding_r: Regex,
-    pub padding_t: Regex,
-    pub padding_b: Regex,
-    pub margin: Regex,
-    pub margin_x: Regex,
-    pub margin_y: Regex,
-    pub margin_t: Regex,
-    pub margin_b: Regex,
-    pub margin_l: Regex,
-    pub margin_r: Regex,
-    pub text_color: Regex,
     pub z_index: Regex,
     pub grid_template_columns: Regex,
     pub grid_template_rows: Regex,
-    pub row_gap: Regex,
-    pub column_gap: Regex,
-    pub gap: Regex,
-    pub left: Regex,
-    pub right: Regex,
-    pub top: Regex,
-    pub bottom: Regex,
     pub col_span: Regex,
     pub row_span: Regex,
     pub custom_font: Regex,
@@ -62,15 +41,14 @@ pub struct TailwindRegex {
     pub border_radius_tr: Regex,
     pub border_radius_bl: Regex,
     pub border_radius_br: Regex,
+    pub outline_width: Regex,
+    pub outline_offset: Regex,
+    pub divide_x: Regex,
+    pub divide_y: Regex,
+    pub scale: Regex,
 }
 
 pub static REGEX: LazyLock<TailwindRegex> = LazyLock::new(|| TailwindRegex {
-    width: Regex::new(r"^w-\[(\d+)px|(\d+)%]$").unwrap(),
-    min_width: Regex::new(r"^min-w-\[(\d+)px|(\d+)%]$").unwrap(),
-    max_width: Regex::new(r"^max-w-\[(\d+)px|(\d+)%]$").unwrap(),
-    height: Regex::new(r"^h-\[(\d+)px|(\d+)%]$").unwrap(),
-    min_height: Regex::new(r"^min-h-\[(\d+)px|(\d+)%]$").unwrap(),
-    max_height: Regex::new(r"^max-h-\[(\d+)px|(\d+)%]$").unwrap(),
     border: Regex::new(r"^border-(\d+)$").unwrap(),
     border_x: Regex::new(r"^border-x-(\d+)$").unwrap(),
     border_y: Regex::new(r"^border-y-(\d+)$").unwrap(),
@@ -78,59 +56,601 @@ pub static REGEX: LazyLock<TailwindRegex> = LazyLock::new(|| TailwindRegex {
     border_r: Regex::new(r"^border-r-(\d+)$").unwrap(),
     border_t: Regex::new(r"^border-t-(\d+)$").unwrap(),
     border_b: Regex::new(r"^border-b-(\d+)$").unwrap(),
-    border_color: Regex::new(
-        r"^border-\[#([0-9a-fA-F]{2})([0-9a-fA-F]{2})([0-9a-fA-F]{2})([0-9a-fA-F]{2})?]$",
-    )
-    .unwrap(),
-    background_color: Regex::new(
-        r"^bg-\[#([0-9a-fA-F]{2})([0-9a-fA-F]{2})([0-9a-fA-F]{2})([0-9a-fA-F]{2})?]$",
-    )
-    .unwrap(),
-    padding: Regex::new(r"^p-\[(\d+)px]$").unwrap(),
-    padding_x: Regex::new(r"px-\[(\d+)px]$").unwrap(),
-    padding_y: Regex::new(r"py-\[(\d+)px]$").unwrap(),
-    padding_t: Regex::new(r"pt-\[(\d+)px]$").unwrap(),
-    padding_b: Regex::new(r"pb-\[(\d+)px]$").unwrap(),
-    padding_l: Regex::new(r"pl-\[(\d+)px]$").unwrap(),
-    padding_r: Regex::new(r"pr-\[(\d+)px]$").unwrap(),
-    margin: Regex::new(r"^m-\[(\d+)px]$").unwrap(),
-    margin_x: Regex::new(r"mx-\[(\d+)px]$").unwrap(),
-    margin_y: Regex::new(r"my-\[(\d+)px]$").unwrap(),
-    margin_t: Regex::new(r"mt-\[(\d+)px]$").unwrap(),
-    margin_b: Regex::new(r"mb-\[(\d+)px]$").unwrap(),
-    margin_l: Regex::new(r"ml-\[(\d+)px]$").unwrap(),
-    margin_r: Regex::new(r"mr-\[(\d+)px]$").unwrap(),
-    text_color: Regex::new(
-        r"text-\[#([0-9a-fA-F]{2})([0-9a-fA-F]{2})([0-9a-fA-F]{2})([0-9a-fA-F]{2})?]$",
-    )
-    .unwrap(),
     z_index: Regex::new(r"^(-)?z-(\d+)$").unwrap(),
-    grid_template_columns: Regex::new(
-        r"^grid-cols-(?:(\d+)|\[((?:\d+fr|\d+px|auto)(?:_\d+fr|_\d+px|_auto)*)])$",
-    )
-    .unwrap(),
-    grid_template_rows: Regex::new(
-        r"^grid-rows-(?:(\d+)|\[((?:\d+fr|\d+px|auto)(?:_\d+fr|_\d+px|_auto)*)])$",
-    )
-    .unwrap(),
-    gap: Regex::new(r"^gap-\[(\d+)px]$").unwrap(),
-    row_gap: Regex::new(r"^gap-y-\[(\d+)px]$").unwrap(),
-    column_gap: Regex::new(r"^gap-x-\[(\d+)px]$").unwrap(),
-    left: Regex::new(r"^(-)?left-\[(\d+)px]$").unwrap(),
-    right: Regex::new(r"^(-)?right-\[(\d+)px]$").unwrap(),
-    top: Regex::new(r"^(-)?top-\[(\d+)px]$").unwrap(),
-    bottom: Regex::new(r"^(-)?bottom-\[(\d+)px]$").unwrap(),
+    grid_template_columns: Regex::new(r"^grid-cols-(?:(\d+)|\[([^\]]+)])$").unwrap(),
+    grid_template_rows: Regex::new(r"^grid-rows-(?:(\d+)|\[([^\]]+)])$").unwrap(),
     col_span: Regex::new(r"^col-span-(\d+)$").unwrap(),
     row_span: Regex::new(r"^row-span-(\d+)$").unwrap(),
     custom_font: Regex::new(r"^font-(\w+)$").unwrap(),
-    font_size: Regex::new(r"^font-size-\[(\d+)px]$").unwrap(),
-    border_radius: Regex::new(r"^border-\[(\d+)px]$").unwrap(),
-    border_radius_bl: Regex::new(r"^border-bl-\[(\d+)px]$").unwrap(),
-    border_radius_br: Regex::new(r"^border-br-\[(\d+)px]$").unwrap(),
-    border_radius_tl: Regex::new(r"^border-tl-\[(\d+)px]$").unwrap(),
-    border_radius_tr: Regex::new(r"^border-tr-\[(\d+)px]$").unwrap(),
+    font_size: Regex::new(r"^(?:font-size|text)-\[([^\]]+)]$").unwrap(),
+    border_radius: Regex::new(r"^border-\[([^\]]+)]$").unwrap(),
+    border_radius_bl: Regex::new(r"^border-bl-\[([^\]]+)]$").unwrap(),
+    border_radius_br: Regex::new(r"^border-br-\[([^\]]+)]$").unwrap(),
+    border_radius_tl: Regex::new(r"^border-tl-\[([^\]]+)]$").unwrap(),
+    border_radius_tr: Regex::new(r"^border-tr-\[([^\]]+)]$").unwrap(),
+    outline_width: Regex::new(r"^outline-(?:(\d+)|\[([^\]]+)])$").unwrap(),
+    outline_offset: Regex::new(r"^outline-offset-(?:(\d+)|\[([^\]]+)])$").unwrap(),
+    divide_x: Regex::new(r"^divide-x(?:-(\d+)|-\[([^\]]+)])?$").unwrap(),
+    divide_y: Regex::new(r"^divide-y(?:-(\d+)|-\[([^\]]+)])?$").unwrap(),
+    scale: Regex::new(r"^scale-(?:([xy])-)?(\d+)$").unwrap(),
 });
 
+/// Errors that can occur while parsing the classes of a node
+#[derive(Error, Debug)]
+pub enum StyleError {
+    #[error("No font registered with name {0}")]
+    UnknownFont(String),
+    #[error("Unsupported style class: {0}")]
+    UnsupportedClass(String),
+    #[error("Unsupported style declaration: {0}")]
+    UnsupportedDeclaration(String),
+}
+
+/// Utilities that take a value of the spacing scale, e.g. `p-4` or `-mt-2`
+const SPACING_UTILITIES: &[&str] = &[
+    "p", "px", "py", "pt", "pr", "pb", "pl", "m", "mx", "my", "mt", "mr", "mb", "ml", "gap",
+    "gap-x", "gap-y", "inset", "inset-x", "inset-y", "top", "right", "bottom", "left", "w",
+    "min-w", "max-w", "h", "min-h", "max-h", "size", "basis", "space-x", "space-y",
+];
+
+/// Spacing utilities that accept negative values, e.g. `-mt-2`
+const NEGATIVE_UTILITIES: &[&str] = &[
+    "m", "mx", "my", "mt", "mr", "mb", "ml", "inset", "inset-x", "inset-y", "top", "right",
+    "bottom", "left", "space-x", "space-y",
+];
+
+/// Spacing utilities that accept fractions (`w-1/2`), `full` and `auto`
+const FRACTION_UTILITIES: &[&str] = &[
+    "inset", "inset-x", "inset-y", "top", "right", "bottom", "left", "w", "min-w", "max-w", "h",
+    "min-h", "max-h", "size", "basis",
+];
+
+/// Split a class of the spacing scale into its utility and value
+///
+/// Supports steps of the scale (`p-4`, `m-0.5`), `px`, fractions (`w-1/2`), `full`,
+/// `auto`, arbitrary values (`p-[1.5rem]`, see [`arbitrary_length`]), spacing tokens of the
+/// theme (`p-gutter`) and negative values (`-mt-2`).
+/// A step of the scale is `0.25rem`.
+/// Returns `None` if the class isn't a spacing utility or the value isn't supported.
+fn spacing_class<'a>(class: &'a str, ctx: &StyleContext) -> Option<(&'a str, Val)> {
+    let rem = ctx.rem;
+    let (negative, class) = match class.strip_prefix('-') {
+        Some(class) => (true, class),
+        None => (false, class),
+    };
+    let (utility, value) = match class.split_once("-[") {
+        Some((utility, _)) => (utility, &class[utility.len() + 1..]),
+        None => class.rsplit_once('-')?,
+    };
+    if !SPACING_UTILITIES.contains(&utility) || negative && !NEGATIVE_UTILITIES.contains(&utility) {
+        return None;
+    }
+    let fractions = FRACTION_UTILITIES.contains(&utility);
+
+    let value = match value {
+        "px" => px(1),
+        "auto" if fractions && !negative => return Some((utility, auto())),
+        "full" if fractions => percent(100),
+        value if ctx.theme.spacing.contains_key(value) => {
+            arbitrary_length(&ctx.theme.spacing[value], rem)?
+        }
+        value if value.starts_with('[') => {
+            arbitrary_length(value.strip_prefix('[')?.strip_suffix(']')?, rem)?
+        }
+        value => match value.split_once('/') {
+            Some((numerator, denominator)) if fractions => {
+                let numerator = numerator.parse::<u32>().ok()?;
+                let denominator = denominator.parse::<u32>().ok().filter(|d| *d != 0)?;
+                percent(numerator as f32 / denominator as f32 * 100.0)
+            }
+            Some(_) => return None,
+            None => {
+                let steps = value.parse::<f32>().ok()?;
+                // Only multiples of a quarter step are part of the scale
+                if !steps.is_finite() || steps < 0.0 || (steps * 4.0).fract() != 0.0 {
+                    return None;
+                }
+                px(steps * 0.25 * rem)
+            }
+        },
+    };
+
+    Some((utility, if negative { negate(value) } else { value }))
+}
+
+/// Negate a length, `auto` stays `auto`
+fn negate(value: Val) -> Val {
+    match value {
+        Val::Auto => Val::Auto,
+        Val::Px(value) => Val::Px(-value),
+        Val::Percent(value) => Val::Percent(-value),
+        Val::Vw(value) => Val::Vw(-value),
+        Val::Vh(value) => Val::Vh(-value),
+        Val::VMin(value) => Val::VMin(-value),
+        Val::VMax(value) => Val::VMax(-value),
+    }
+}
+
+/// Parse the value of an arbitrary length like `12px`, `50%`, `1.5rem` or `10vw`
+///
+/// Supported units are `px`, `%`, `vw`, `vh`, `vmin`, `vmax` and `rem`, plain numbers are
+/// pixels. `rem` is the size of the root font in pixels.
+pub fn arbitrary_length(value: &str, rem: f32) -> Option<Val> {
+    let number = |suffix: &str| -> Option<f32> {
+        let number = value.strip_suffix(suffix)?.parse::<f32>().ok()?;
+        number.is_finite().then_some(number)
+    };
+
+    // The order matters, `vmin` and `vmax` have to be checked before `px`
+    if let Some(value) = number("vmin") {
+        Some(Val::VMin(value))
+    } else if let Some(value) = number("vmax") {
+        Some(Val::VMax(value))
+    } else if let Some(value) = number("rem") {
+        Some(Val::Px(value * rem))
+    } else if let Some(value) = number("px") {
+        Some(Val::Px(value))
+    } else if let Some(value) = number("%") {
+        Some(Val::Percent(value))
+    } else if let Some(value) = number("vw") {
+        Some(Val::Vw(value))
+    } else if let Some(value) = number("vh") {
+        Some(Val::Vh(value))
+    } else {
+        number("").map(Val::Px)
+    }
+}
+
+/// Parse a font size, which has to be absolute
+///
+/// Besides `px` and `rem`, `em` and `%` are accepted which are relative to the inherited
+/// font size.
+pub(crate) fn font_size(value: &str, rem: f32, inherited: f32) -> Option<f32> {
+    let size = if let Some(em) = value.strip_suffix("em").filter(|v| !v.ends_with('r')) {
+        em.parse::<f32>().ok()? * inherited
+    } else {
+        match arbitrary_length(value, rem)? {
+            Val::Px(size) => size,
+            Val::Percent(percent) => percent / 100.0 * inherited,
+            _ => return None,
+        }
+    };
+
+    (size.is_finite() && size >= 0.0).then_some(size)
+}
+
+/// Length of a regex with a whole pixel value in the first and an arbitrary value in the
+/// second capture group
+fn captures_length(captures: &Captures, rem: f32) -> Option<Val> {
+    match (captures.get(1), captures.get(2)) {
+        (Some(pixels), _) => Some(px(pixels.as_str().parse::<f32>().ok()?)),
+        (None, Some(arbitrary)) => arbitrary_length(arbitrary.as_str(), rem),
+        (None, None) => None,
+    }
+}
+
+/// Width of a `divide-x` or `divide-y` class, one pixel if it has no value
+fn divider_width(captures: &Captures, rem: f32) -> Option<Val> {
+    match (captures.get(1), captures.get(2)) {
+        (None, None) => Some(px(1)),
+        _ => captures_length(captures, rem),
+    }
+}
+
+/// Grid tracks of a regex with a track count in the first and a list of tracks separated by
+/// underscores in the second capture group, e.g. `grid-cols-3` or `grid-cols-[1fr_20%_auto]`
+fn captures_grid_tracks(captures: &Captures, rem: f32) -> Option<Vec<RepeatedGridTrack>> {
+    if let Some(count) = captures.get(1) {
+        return Some(RepeatedGridTrack::auto(count.as_str().parse::<u16>().ok()?));
+    }
+
+    grid_tracks(captures.get(2)?.as_str().split('_'), rem)
+}
+
+/// Grid tracks of a list like `1fr 20% auto`, each part is a single track
+pub(crate) fn grid_tracks<'a>(
+    parts: impl Iterator<Item = &'a str>,
+    rem: f32,
+) -> Option<Vec<RepeatedGridTrack>> {
+    let mut tracks = vec![];
+    for part in parts {
+        let track = if part == "auto" {
+            RepeatedGridTrack::auto(1)
+        } else if let Some(fraction) = part.strip_suffix("fr") {
+            RepeatedGridTrack::fr(1, fraction.parse::<f32>().ok()?)
+        } else {
+            match arbitrary_length(part, rem)? {
+                Val::Auto => RepeatedGridTrack::auto(1),
+                Val::Px(value) => RepeatedGridTrack::px(1, value),
+                Val::Percent(value) => RepeatedGridTrack::percent(1, value),
+                Val::Vw(value) => RepeatedGridTrack::vw(1, value),
+                Val::Vh(value) => RepeatedGridTrack::vh(1, value),
+                Val::VMin(value) => RepeatedGridTrack::vmin(1, value),
+                Val::VMax(value) => RepeatedGridTrack::vmax(1, value),
+            }
+        };
+        tracks.push(track);
+    }
+
+    Some(tracks)
+}
+
+/// Utilities that take a border radius, corners and sides come before the whole node
+const RADIUS_UTILITIES: &[&str] = &[
+    "rounded-tl",
+    "rounded-tr",
+    "rounded-br",
+    "rounded-bl",
+    "rounded-t",
+    "rounded-r",
+    "rounded-b",
+    "rounded-l",
+    "rounded",
+];
+
+/// Split a border radius class like `rounded-lg` or `rounded-tl-[12px]` into utility and radius
+///
+/// The value is a radius of the theme, `none`, `full` or an arbitrary length.
+fn radius_class<'a>(class: &'a str, ctx: &StyleContext) -> Option<(&'a str, Val)> {
+    let (utility, value) = RADIUS_UTILITIES.iter().find_map(|utility| {
+        let value = class.strip_prefix(utility)?.strip_prefix('-')?;
+        Some((*utility, value))
+    })?;
+
+    let radius = match value {
+        "none" => px(0),
+        "full" => px(f32::MAX),
+        value => match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            Some(arbitrary) => arbitrary_length(arbitrary, ctx.rem)?,
+            None => arbitrary_length(ctx.theme.radius.get(value)?, ctx.rem)?,
+        },
+    };
+
+    Some((utility, radius))
+}
+
+/// Utilities that take a color, sides of the border come before the whole border
+const COLOR_UTILITIES: &[&str] = &[
+    "bg", "text", "border-x", "border-y", "border-t", "border-r", "border-b", "border-l", "border",
+    "outline", "divide",
+];
+
+/// Split a color class like `bg-slate-800/50` or `text-[rgb(255_0_0)]` into utility and color
+///
+/// The value is either a color of the theme, a color of the Tailwind palette, `white`, `black`,
+/// `transparent` or an arbitrary CSS color, optionally followed by an opacity modifier
+/// (`/50` or `/[0.35]`).
+fn color_class<'a>(class: &'a str, theme: &HtmlTheme) -> Option<(&'a str, Color)> {
+    let (utility, value) = COLOR_UTILITIES.iter().find_map(|utility| {
+        let value = class.strip_prefix(utility)?.strip_prefix('-')?;
+        Some((*utility, value))
+    })?;
+
+    let (color, opacity) = match value.strip_prefix('[') {
+        Some(arbitrary) => {
+            let (color, rest) = arbitrary.split_once(']')?;
+            let opacity = match rest {
+                "" => None,
+                rest => Some(rest.strip_prefix('/')?),
+            };
+            (parse_css_color(&color.replace('_', " "))?, opacity)
+        }
+        None => {
+            let (name, opacity) = match value.split_once('/') {
+                Some((name, opacity)) => (name, Some(opacity)),
+                None => (value, None),
+            };
+            let color = match name {
+                name if theme.colors.contains_key(name) => parse_css_color(&theme.colors[name])?,
+                "transparent" => Color::NONE,
+                "white" => Color::WHITE,
+                "black" => Color::BLACK,
+                name => {
+                    let (name, shade) = name.rsplit_once('-')?;
+                    tailwind_color(name, shade)?.into()
+                }
+            };
+            (color, opacity)
+        }
+    };
+
+    let Some(opacity) = opacity else {
+        return Some((utility, color));
+    };
+    Some((utility, color.with_alpha(self::opacity(opacity)?)))
+}
+
+/// Parse an opacity of the scale like `50` or an arbitrary one like `[0.35]` or `[35%]`
+fn opacity(value: &str) -> Option<f32> {
+    match value.strip_prefix('[').and_then(|o| o.strip_suffix(']')) {
+        Some(arbitrary) => arbitrary_opacity(arbitrary),
+        None => Some(value.parse::<u8>().ok().filter(|o| *o <= 100)? as f32 / 100.0),
+    }
+}
+
+/// Parse an arbitrary opacity, a number between 0 and 1 or a percentage
+pub(crate) fn arbitrary_opacity(value: &str) -> Option<f32> {
+    let opacity = match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok()? / 100.0,
+        None => value.parse::<f32>().ok()?,
+    };
+
+    opacity.is_finite().then(|| opacity.clamp(0.0, 1.0))
+}
+
+/// Everything besides the classes that is required to parse a [`Style`]
+///
+/// It is shared by all nodes of a document, so their style can be resolved again at runtime.
+#[derive(Debug, Clone)]
+pub struct StyleContext {
+    /// Fonts registered in the head of the document
+    pub fonts: HashMap<String, FontFamily>,
+    /// Font used if no other font is set
+    pub default_font: FontFamily,
+    /// How unsupported classes are handled
+    pub unsupported: UnsupportedClasses,
+    /// Size of `1rem` in pixels
+    pub rem: f32,
+    /// Design tokens
+    pub theme: HtmlTheme,
+    /// Component classes of the stylesheets of the document
+    pub stylesheet: Stylesheet,
+}
+
+/// How unsupported classes and variants are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsupportedClasses {
+    /// Fail with [`StyleError::UnsupportedClass`]
+    Error,
+    /// Log a warning and skip the class
+    Warn,
+    /// Skip the class silently, e.g. if it was already reported while loading
+    Ignore,
+}
+
+/// Handle an unsupported class, declaration or selector
+pub(crate) fn handle_unsupported<E: std::fmt::Display>(
+    error: E,
+    unsupported: UnsupportedClasses,
+) -> Result<(), E> {
+    match unsupported {
+        UnsupportedClasses::Error => Err(error),
+        UnsupportedClasses::Warn => {
+            warn!("{error}");
+            Ok(())
+        }
+        UnsupportedClasses::Ignore => Ok(()),
+    }
+}
+
+/// State of a node that decides which variants like `hover:` are active
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VariantState {
+    /// State of the node itself
+    pub element: ElementState,
+    /// State of the closest ancestor with the `group` class
+    pub group: ElementState,
+    /// State of the closest previous sibling with the `peer` class
+    pub peer: ElementState,
+    pub position: SiblingPosition,
+    /// Logical size of the viewport the node is rendered to, zero if unknown
+    pub viewport: UVec2,
+    pub color_scheme: HtmlColorScheme,
+}
+
+/// Interaction state of a single node
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ElementState {
+    /// The pointer is over the node
+    pub hovered: bool,
+    /// The node is pressed
+    pub pressed: bool,
+    /// The node has the input focus
+    pub focused: bool,
+    /// The node is disabled
+    pub disabled: bool,
+    /// The node is a checked checkbox or radio button
+    pub checked: bool,
+}
+
+impl ElementState {
+    /// Whether the variant is active, `None` if it is not an interaction variant
+    fn matches(&self, variant: &str) -> Option<bool> {
+        match variant {
+            "hover" => Some(self.hovered),
+            "active" => Some(self.pressed),
+            "focus" => Some(self.focused),
+            "disabled" => Some(self.disabled),
+            "checked" => Some(self.checked),
+            _ => None,
+        }
+    }
+}
+
+/// Position of a node among its siblings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SiblingPosition {
+    /// Index of the node, starting at zero
+    pub index: usize,
+    /// Number of siblings including the node
+    pub count: usize,
+}
+
+impl SiblingPosition {
+    /// Whether the variant is active, `None` if it is not a structural variant
+    ///
+    /// Like in CSS, `odd:` applies to the first, third, … node.
+    fn matches(&self, variant: &str) -> Option<bool> {
+        match variant {
+            "first" => Some(self.index == 0),
+            "last" => Some(self.index + 1 == self.count),
+            "only" => Some(self.count == 1),
+            "odd" => Some(self.index.is_multiple_of(2)),
+            "even" => Some(!self.index.is_multiple_of(2)),
+            _ => None,
+        }
+    }
+
+    /// Whether the same structural variants apply
+    fn same_variants(&self, other: &SiblingPosition) -> bool {
+        ["first", "last", "only", "odd"]
+            .iter()
+            .all(|variant| self.matches(variant) == other.matches(variant))
+    }
+}
+
+/// Supported variants in the order their classes are applied, later ones take precedence
+///
+/// This is the order of Tailwind, e.g. `disabled:` overrides `hover:` and both override
+/// `group-hover:`. Breakpoints follow from the smallest to the largest, color schemes like
+/// `dark:` come last.
+const VARIANT_ORDER: &[&str] = &[
+    "group",
+    "peer",
+    "first",
+    "last",
+    "only",
+    "odd",
+    "even",
+    "checked",
+    "hover",
+    "focus",
+    "active",
+    "disabled",
+    "portrait",
+    "landscape",
+];
+
+/// Position of a class with the variants in [`VARIANT_ORDER`]
+fn variant_order(variants: &[&str], theme: &HtmlTheme) -> usize {
+    variants
+        .iter()
+        .map(|variant| {
+            let name = match variant.split_once('-') {
+                Some((prefix @ ("group" | "peer"), _)) => prefix,
+                _ => variant,
+            };
+            if let Some(position) = VARIANT_ORDER.iter().position(|known| *known == name) {
+                return position;
+            }
+            match theme.breakpoints.get(*variant) {
+                Some(min_width) => {
+                    let smaller = theme
+                        .breakpoints
+                        .values()
+                        .filter(|other| *other < min_width)
+                        .count();
+                    VARIANT_ORDER.len() + smaller
+                }
+                None => VARIANT_ORDER.len() + theme.breakpoints.len(),
+            }
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+impl VariantState {
+    /// Whether all variants are active, `None` if a variant is not supported
+    ///
+    /// Structural variants like `first:` apply by the position among the siblings.
+    /// Interaction variants like `hover:` can be prefixed with `group-` or `peer-` to apply
+    /// by the state of the group or peer of the node.
+    /// Breakpoints like `md:` are taken from the theme and apply from their width upwards.
+    /// Color schemes like `dark:` apply while they are the [`HtmlColorScheme`].
+    pub fn matches(&self, variants: &[&str], theme: &HtmlTheme) -> Option<bool> {
+        let mut active = true;
+        for variant in variants {
+            if let Some(element) = self.element.matches(variant) {
+                active &= element;
+                continue;
+            }
+            if let Some(position) = self.position.matches(variant) {
+                active &= position;
+                continue;
+            }
+
+            active &= match *variant {
+                "portrait" => self.portrait(),
+                "landscape" => !self.portrait(),
+                variant => {
+                    if let Some(state) = variant.strip_prefix("group-") {
+                        self.group.matches(state)?
+                    } else if let Some(state) = variant.strip_prefix("peer-") {
+                        self.peer.matches(state)?
+                    } else if let Some(min_width) = theme.breakpoints.get(variant) {
+                        self.viewport.x as f32 >= *min_width
+                    } else {
+                        self.color_scheme.matches(variant, theme)?
+                    }
+                }
+            };
+        }
+
+        Some(active)
+    }
+
+    fn portrait(&self) -> bool {
+        self.viewport.y > self.viewport.x
+    }
+
+    /// Whether the variants active in the other state differ, i.e. the style of a node with
+    /// the classes changes
+    ///
+    /// Resizing the viewport only matters if it crosses a breakpoint or changes the
+    /// orientation. Changing the color scheme only matters if a class has a color scheme
+    /// variant.
+    pub fn differs(&self, other: &VariantState, classes: &str, ctx: &StyleContext) -> bool {
+        let theme = &ctx.theme;
+        let crosses_breakpoint = theme.breakpoints.values().any(|min_width| {
+            (self.viewport.x as f32 >= *min_width) != (other.viewport.x as f32 >= *min_width)
+        });
+
+        self.element != other.element
+            || self.group != other.group
+            || self.peer != other.peer
+            || !self.position.same_variants(&other.position)
+            || self.portrait() != other.portrait()
+            || crosses_breakpoint
+            || (self.color_scheme != other.color_scheme && uses_color_scheme(classes, ctx))
+    }
+}
+
+/// Whether any of the classes, or the utilities of a component class, has a color scheme
+/// variant like `dark:`
+fn uses_color_scheme(classes: &str, ctx: &StyleContext) -> bool {
+    ctx.stylesheet.expand(classes).iter().any(|class| {
+        split_variants(class).0.iter().any(|variant| {
+            HtmlColorScheme::Light
+                .matches(variant, &ctx.theme)
+                .is_some()
+        })
+    })
+}
+
+/// Split a class into its variants and the utility, e.g. `hover:bg-white` into `["hover"]`
+/// and `bg-white`
+///
+/// Colons inside of arbitrary values don't separate variants.
+pub fn split_variants(class: &str) -> (Vec<&str>, &str) {
+    let mut variants = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (index, c) in class.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 => {
+                variants.push(&class[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    (variants, &class[start..])
+}
+
 #[derive(Debug, Bundle, Reflect)]
 pub struct TailwindNodeBundle {
     pub node: Node,
@@ -142,6 +662,8 @@ pub struct TailwindNodeBundle {
     pub text_layout: TextLayout,
     pub text_font: TextFont,
     pub line_height: LineHeight,
+    pub outline: Outline,
+    pub transform: UiTransform,
 }
 
 #[derive(Debug, Clone)]
@@ -182,7 +704,33 @@ pub struct Style {
     pub text_layout: TextLayout,
     pub line_height: LineHeight,
     pub text_font: TextFont,
+    /// Name of the registered font, the default font is used if not set
+    pub font_family: Option<String>,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
     pub border_radius: BorderRadius,
+    pub flex_basis: Val,
+    pub outline_width: Val,
+    pub outline_offset: Val,
+    /// Color of the outline, the text color is used if not set
+    pub outline_color: Option<Color>,
+    /// Horizontal margin between the children, `space-x-*`
+    pub space_x: Option<Val>,
+    /// Vertical margin between the children, `space-y-*`
+    pub space_y: Option<Val>,
+    /// Width of the vertical borders between the children, `divide-x-*`
+    pub divide_x: Option<Val>,
+    /// Width of the horizontal borders between the children, `divide-y-*`
+    pub divide_y: Option<Val>,
+    /// Color of the borders between the children, the text color is used if not set
+    pub divide_color: Option<Color>,
+    /// Opacity of the node, `opacity-*`
+    pub opacity: f32,
+    /// Opacity of the ancestors, it fades the node and its descendants as well
+    pub inherited_opacity: f32,
+    /// Scale of the node around its center, `scale-*`
+    pub scale: Vec2,
 }
 
 impl Default for Style {
@@ -224,1036 +772,816 @@ impl Default for Style {
             text_layout: TextLayout::default(),
             line_height: LineHeight::default(),
             text_font: TextFont::default(),
+            font_family: None,
+            italic: false,
+            underline: false,
+            strikethrough: false,
             border_radius: BorderRadius::default(),
+            flex_basis: auto(),
+            outline_width: px(0),
+            outline_offset: px(0),
+            outline_color: None,
+            space_x: None,
+            space_y: None,
+            divide_x: None,
+            divide_y: None,
+            divide_color: None,
+            opacity: 1.0,
+            inherited_opacity: 1.0,
+            scale: Vec2::ONE,
         }
     }
 }
 
 impl Style {
+    /// Parse the classes of a node
+    ///
+    /// Text properties are inherited from the parent if there is one.
+    /// Classes with variants like `hover:` are only applied if all of their variants are
+    /// active in the state. The declarations of an inline style attribute are applied last.
     pub fn parse(
         classes: &str,
-        fonts: &HashMap<String, Handle<Font>>,
-        default_font: Option<Handle<Font>>,
-    ) -> Self {
-        let classes = classes
-            .trim()
-            .split_ascii_whitespace()
-            .filter(|c| !c.is_empty())
-            .collect::<Vec<_>>();
-
-        let mut style = Style::default();
-        if let Some(default_font) = default_font {
-            style.text_font = TextFont {
-                font: default_font,
-                ..Default::default()
-            };
+        inline: &str,
+        ctx: &StyleContext,
+        state: &VariantState,
+        parent: Option<&Style>,
+    ) -> Result<Self, StyleError> {
+        let mut style = match parent {
+            Some(parent) => Self::inherit(parent),
+            None => Self::default(),
+        };
+        if let Some(parent) = parent
+            && state.position.index > 0
+        {
+            parent.separate_child(
+                &mut style.margin,
+                &mut style.border,
+                &mut style.border_color,
+            );
         }
 
-        for class in classes {
-            match class {
-                "visible" => style.visibility = Visibility::Visible,
-                "invisible" => style.visibility = Visibility::Hidden,
-
-                "relative" => style.position = PositionType::Relative,
-                "absolute" => style.position = PositionType::Absolute,
-
-                "block" => style.display = Display::Block,
-                "grid" => style.display = Display::Grid,
-                "flex" => style.display = Display::Flex,
-                "hidden" => style.display = Display::None,
-
-                "flex-col" => style.flex_direction = FlexDirection::Column,
-                "flex-row" => style.flex_direction = FlexDirection::Row,
-                "flex-row-reverse" => style.flex_direction = FlexDirection::RowReverse,
-                "flex-col-reverse" => style.flex_direction = FlexDirection::ColumnReverse,
-
-                "justify-start" => style.justify_content = JustifyContent::FlexStart,
-                "justify-center" => style.justify_content = JustifyContent::Center,
-                "justify-end" => style.justify_content = JustifyContent::FlexEnd,
-                "justify-between" => style.justify_content = JustifyContent::SpaceBetween,
-                "justify-around" => style.justify_content = JustifyContent::SpaceAround,
-                "justify-evenly" => style.justify_content = JustifyContent::SpaceEvenly,
-                "justify-stretch" => style.justify_content = JustifyContent::Stretch,
-                "justify-normal" => style.justify_content = JustifyContent::Default,
-
-                "justify-items-start" => style.justify_items = JustifyItems::Start,
-                "justify-items-end" => style.justify_items = JustifyItems::End,
-                "justify-items-center" => style.justify_items = JustifyItems::Center,
-                "justify-items-stretch" => style.justify_items = JustifyItems::Stretch,
-                "justify-items-normal" => style.justify_items = JustifyItems::Default,
-
-                "justify-self-auto" => style.justify_self = JustifySelf::Auto,
-                "justify-self-start" => style.justify_self = JustifySelf::Start,
-                "justify-self-end" => style.justify_self = JustifySelf::End,
-                "justify-self-center" => style.justify_self = JustifySelf::Center,
-                "justify-self-stretch" => style.justify_self = JustifySelf::Stretch,
-
-                "content-normal" => style.align_content = AlignContent::Default,
-                "content-center" => style.align_content = AlignContent::Center,
-                "content-start" => style.align_content = AlignContent::FlexStart,
-                "content-end" => style.align_content = AlignContent::FlexEnd,
-                "content-between" => style.align_content = AlignContent::SpaceBetween,
-                "content-around" => style.align_content = AlignContent::SpaceAround,
-                "content-evenly" => style.align_content = AlignContent::SpaceEvenly,
-                "content-stretch" => style.align_content = AlignContent::Stretch,
-
-                "items-start" => style.align_items = AlignItems::FlexStart,
-                "items-end" => style.align_items = AlignItems::FlexEnd,
-                "items-center" => style.align_items = AlignItems::Center,
-                "items-stretch" => style.align_items = AlignItems::Stretch,
-                "items-baseline" => style.align_items = AlignItems::Baseline,
-
-                "self-auto" => style.align_self = AlignSelf::Auto,
-                "self-start" => style.align_self = AlignSelf::FlexStart,
-                "self-end" => style.align_self = AlignSelf::FlexEnd,
-                "self-center" => style.align_self = AlignSelf::Center,
-                "self-stretch" => style.align_self = AlignSelf::Stretch,
-                "self-baseline" => style.align_self = AlignSelf::Baseline,
-
-                "place-content-center" => {
-                    style.align_content = AlignContent::Center;
-                    style.justify_content = JustifyContent::Center;
-                }
-                "place-content-start" => {
-                    style.align_content = AlignContent::FlexStart;
-                    style.justify_content = JustifyContent::FlexStart;
-                }
-                "place-content-end" => {
-                    style.align_content = AlignContent::FlexEnd;
-                    style.justify_content = JustifyContent::FlexEnd;
-                }
-                "place-content-between" => {
-                    style.align_content = AlignContent::SpaceBetween;
-                    style.justify_content = JustifyContent::SpaceBetween;
-                }
-                "place-content-around" => {
-                    style.align_content = AlignContent::SpaceAround;
-                    style.justify_content = JustifyContent::SpaceAround;
-                }
-                "place-content-evenly" => {
-                    style.align_content = AlignContent::SpaceEvenly;
-                    style.justify_content = JustifyContent::SpaceEvenly;
-                }
-                "place-content-stretch" => {
-                    style.align_content = AlignContent::Stretch;
-                    style.justify_content = JustifyContent::Stretch;
-                }
+        Self::apply_classes(style, classes, inline, ctx, state)
+    }
 
-                "place-items-start" => {
-                    style.align_items = AlignItems::FlexStart;
-                    style.justify_items = JustifyItems::Start;
-                }
-                "place-items-end" => {
-                    style.align_items = AlignItems::FlexEnd;
-                    style.justify_items = JustifyItems::End;
-                }
-                "place-items-center" => {
-                    style.align_items = AlignItems::Center;
-                    style.justify_items = JustifyItems::Center;
-                }
-                "place-items-stretch" => {
-                    style.align_items = AlignItems::Stretch;
-                    style.justify_items = JustifyItems::Stretch;
-                }
-                "place-items-baseline" => {
-                    style.align_items = AlignItems::Baseline;
-                    style.justify_items = JustifyItems::Baseline;
-                }
+    /// Apply the margin and border of `space-*` and `divide-*` to a child that follows
+    /// another child
+    ///
+    /// The classes of the child itself take precedence.
+    pub fn separate_child(
+        &self,
+        margin: &mut UiRect,
+        border: &mut UiRect,
+        border_color: &mut BorderColor,
+    ) {
+        let divide_color = self.divide_color.unwrap_or(self.text_color.0);
+
+        if let Some(space) = self.space_x {
+            margin.left = space;
+        }
+        if let Some(space) = self.space_y {
+            margin.top = space;
+        }
+        if let Some(width) = self.divide_x {
+            border.left = width;
+            border_color.left = divide_color;
+        }
+        if let Some(width) = self.divide_y {
+            border.top = width;
+            border_color.top = divide_color;
+        }
+    }
 
-                "place-self-auto" => {
-                    style.align_self = AlignSelf::Auto;
-                    style.justify_self = JustifySelf::Auto;
-                }
-                "place-self-start" => {
-                    style.align_self = AlignSelf::FlexStart;
-                    style.justify_self = JustifySelf::Start;
-                }
-                "place-self-end" => {
-                    style.align_self = AlignSelf::FlexEnd;
-                    style.justify_self = JustifySelf::End;
-                }
-                "place-self-center" => {
-                    style.align_self = AlignSelf::Center;
-                    style.justify_self = JustifySelf::Center;
-                }
-                "place-self-stretch" => {
-                    style.align_self = AlignSelf::Stretch;
-                    style.justify_self = JustifySelf::Stretch;
-                }
+    /// Undo [`Self::separate_child`] for a child that doesn't follow another child anymore
+    pub fn reset_separation(
+        &self,
+        margin: &mut UiRect,
+        border: &mut UiRect,
+        border_color: &mut BorderColor,
+    ) {
+        if self.space_x.is_some() {
+            margin.left = Val::ZERO;
+        }
+        if self.space_y.is_some() {
+            margin.top = Val::ZERO;
+        }
+        if self.divide_x.is_some() {
+            border.left = Val::ZERO;
+            border_color.left = BorderColor::DEFAULT.left;
+        }
+        if self.divide_y.is_some() {
+            border.top = Val::ZERO;
+            border_color.top = BorderColor::DEFAULT.top;
+        }
+    }
 
-                "w-full" => style.width = percent(100.0),
-                "h-full" => style.height = percent(100.0),
-                "size-full" => {
-                    style.width = percent(100);
-                    style.height = percent(100.0);
-                }
+    /// Whether the other style applies the same `space-*` and `divide-*` to its children
+    pub fn same_separation(&self, other: &Style) -> bool {
+        self.space_x == other.space_x
+            && self.space_y == other.space_y
+            && self.divide_x == other.divide_x
+            && self.divide_y == other.divide_y
+            && (self.divide_x.is_none() && self.divide_y.is_none()
+                || self.divide_color.unwrap_or(self.text_color.0)
+                    == other.divide_color.unwrap_or(other.text_color.0))
+    }
 
-                "border-black" => style.border_color = BorderColor::all(Color::BLACK),
-                "border-white" => style.border_color = BorderColor::all(Color::WHITE),
+    /// Whether the style applies `space-*` or `divide-*` to its children
+    pub fn separates_children(&self) -> bool {
+        self.space_x.is_some()
+            || self.space_y.is_some()
+            || self.divide_x.is_some()
+            || self.divide_y.is_some()
+    }
 
-                "border" => style.border = UiRect::all(px(1)),
-                "border-x" => {
-                    style.border = UiRect {
-                        left: px(1.0),
-                        right: px(1.0),
-                        ..style.border
-                    }
-                }
-                "border-y" => {
-                    style.border = UiRect {
-                        top: px(1.0),
-                        bottom: px(1.0),
-                        ..style.border
-                    }
-                }
-                "border-l" => {
-                    style.border = UiRect {
-                        left: px(1.0),
-                        ..style.border
-                    }
-                }
-                "border-r" => {
-                    style.border = UiRect {
-                        right: px(1.0),
-                        ..style.border
-                    }
-                }
-                "border-b" => {
-                    style.border = UiRect {
-                        bottom: px(1.0),
-                        ..style.border
-                    }
+    /// Default style with the text properties of the parent
+    ///
+    /// Inherited are the text color, font, font size, weight, line height, alignment and
+    /// text decorations. The opacity of the parent fades the child as well.
+    fn inherit(parent: &Style) -> Self {
+        Style {
+            text_color: parent.text_color,
+            text_font: parent.text_font.clone(),
+            font_family: parent.font_family.clone(),
+            italic: parent.italic,
+            underline: parent.underline,
+            strikethrough: parent.strikethrough,
+            line_height: parent.line_height,
+            text_layout: parent.text_layout,
+            inherited_opacity: parent.inherited_opacity * parent.opacity,
+            ..Default::default()
+        }
+    }
+
+    fn apply_classes(
+        mut style: Style,
+        classes: &str,
+        inline: &str,
+        ctx: &StyleContext,
+        state: &VariantState,
+    ) -> Result<Self, StyleError> {
+        // Classes with variants are applied last, so they override the classes without
+        let classes = ctx.stylesheet.expand(classes);
+        let (base, mut variants): (Vec<_>, Vec<_>) = classes
+            .iter()
+            .map(|class| class.as_ref())
+            .partition(|class| split_variants(class).0.is_empty());
+        variants.sort_by_key(|class| variant_order(&split_variants(class).0, &ctx.theme));
+
+        for class in base {
+            style.apply_supported_class(class, ctx)?;
+        }
+        for class in variants {
+            let (variants, utility) = split_variants(class);
+            match state.matches(&variants, &ctx.theme) {
+                Some(true) => style.apply_supported_class(utility, ctx)?,
+                // Classes of inactive variants are validated nonetheless
+                Some(false) => Style::default().apply_supported_class(utility, ctx)?,
+                None => handle_unsupported(
+                    StyleError::UnsupportedClass(class.to_string()),
+                    ctx.unsupported,
+                )?,
+            }
+        }
+
+        style.apply_declarations(inline, ctx)?;
+
+        // Pick the face of the font family matching weight and style
+        let family = match &style.font_family {
+            Some(name) => ctx.fonts.get(name),
+            None => Some(&ctx.default_font),
+        };
+        if let Some(font) = family.and_then(|family| {
+            family.face(style.text_font.weight >= FontWeight::SEMIBOLD, style.italic)
+        }) {
+            style.text_font.font = font;
+        }
+
+        Ok(style)
+    }
+
+    /// Apply a single class without variants, unsupported classes are handled according to
+    /// the context
+    fn apply_supported_class(&mut self, class: &str, ctx: &StyleContext) -> Result<(), StyleError> {
+        match self.apply_class(class, ctx) {
+            Err(error @ StyleError::UnsupportedClass(_)) => {
+                handle_unsupported(error, ctx.unsupported)
+            }
+            result => result,
+        }
+    }
+
+    /// Apply a single class without variants
+    fn apply_class(&mut self, class: &str, ctx: &StyleContext) -> Result<(), StyleError> {
+        match class {
+            "visible" => self.visibility = Visibility::Visible,
+            "invisible" => self.visibility = Visibility::Hidden,
+
+            // Markers for the `group-*` and `peer-*` variants of other nodes
+            "group" | "peer" => {}
+
+            "relative" => self.position = PositionType::Relative,
+            "absolute" => self.position = PositionType::Absolute,
+
+            "block" => self.display = Display::Block,
+            "grid" => self.display = Display::Grid,
+            "flex" => self.display = Display::Flex,
+            "hidden" => self.display = Display::None,
+
+            "flex-col" => self.flex_direction = FlexDirection::Column,
+            "flex-row" => self.flex_direction = FlexDirection::Row,
+            "flex-row-reverse" => self.flex_direction = FlexDirection::RowReverse,
+            "flex-col-reverse" => self.flex_direction = FlexDirection::ColumnReverse,
+
+            "justify-start" => self.justify_content = JustifyContent::FlexStart,
+            "justify-center" => self.justify_content = JustifyContent::Center,
+            "justify-end" => self.justify_content = JustifyContent::FlexEnd,
+            "justify-between" => self.justify_content = JustifyContent::SpaceBetween,
+            "justify-around" => self.justify_content = JustifyContent::SpaceAround,
+            "justify-evenly" => self.justify_content = JustifyContent::SpaceEvenly,
+            "justify-stretch" => self.justify_content = JustifyContent::Stretch,
+            "justify-normal" => self.justify_content = JustifyContent::Default,
+
+            "justify-items-start" => self.justify_items = JustifyItems::Start,
+            "justify-items-end" => self.justify_items = JustifyItems::End,
+            "justify-items-center" => self.justify_items = JustifyItems::Center,
+            "justify-items-stretch" => self.justify_items = JustifyItems::Stretch,
+            "justify-items-normal" => self.justify_items = JustifyItems::Default,
+
+            "justify-self-auto" => self.justify_self = JustifySelf::Auto,
+            "justify-self-start" => self.justify_self = JustifySelf::Start,
+            "justify-self-end" => self.justify_self = JustifySelf::End,
+            "justify-self-center" => self.justify_self = JustifySelf::Center,
+            "justify-self-stretch" => self.justify_self = JustifySelf::Stretch,
+
+            "content-normal" => self.align_content = AlignContent::Default,
+            "content-center" => self.align_content = AlignContent::Center,
+            "content-start" => self.align_content = AlignContent::FlexStart,
+            "content-end" => self.align_content = AlignContent::FlexEnd,
+            "content-between" => self.align_content = AlignContent::SpaceBetween,
+            "content-around" => self.align_content = AlignContent::SpaceAround,
+            "content-evenly" => self.align_content = AlignContent::SpaceEvenly,
+            "content-stretch" => self.align_content = AlignContent::Stretch,
+
+            "items-start" => self.align_items = AlignItems::FlexStart,
+            "items-end" => self.align_items = AlignItems::FlexEnd,
+            "items-center" => self.align_items = AlignItems::Center,
+            "items-stretch" => self.align_items = AlignItems::Stretch,
+            "items-baseline" => self.align_items = AlignItems::Baseline,
+
+            "self-auto" => self.align_self = AlignSelf::Auto,
+            "self-start" => self.align_self = AlignSelf::FlexStart,
+            "self-end" => self.align_self = AlignSelf::FlexEnd,
+            "self-center" => self.align_self = AlignSelf::Center,
+            "self-stretch" => self.align_self = AlignSelf::Stretch,
+            "self-baseline" => self.align_self = AlignSelf::Baseline,
+
+            "place-content-center" => {
+                self.align_content = AlignContent::Center;
+                self.justify_content = JustifyContent::Center;
+            }
+            "place-content-start" => {
+                self.align_content = AlignContent::FlexStart;
+                self.justify_content = JustifyContent::FlexStart;
+            }
+            "place-content-end" => {
+                self.align_content = AlignContent::FlexEnd;
+                self.justify_content = JustifyContent::FlexEnd;
+            }
+            "place-content-between" => {
+                self.align_content = AlignContent::SpaceBetween;
+                self.justify_content = JustifyContent::SpaceBetween;
+            }
+            "place-content-around" => {
+                self.align_content = AlignContent::SpaceAround;
+                self.justify_content = JustifyContent::SpaceAround;
+            }
+            "place-content-evenly" => {
+                self.align_content = AlignContent::SpaceEvenly;
+                self.justify_content = JustifyContent::SpaceEvenly;
+            }
+            "place-content-stretch" => {
+                self.align_content = AlignContent::Stretch;
+                self.justify_content = JustifyContent::Stretch;
+            }
+
+            "place-items-start" => {
+                self.align_items = AlignItems::FlexStart;
+                self.justify_items = JustifyItems::Start;
+            }
+            "place-items-end" => {
+                self.align_items = AlignItems::FlexEnd;
+                self.justify_items = JustifyItems::End;
+            }
+            "place-items-center" => {
+                self.align_items = AlignItems::Center;
+                self.justify_items = JustifyItems::Center;
+            }
+            "place-items-stretch" => {
+                self.align_items = AlignItems::Stretch;
+                self.justify_items = JustifyItems::Stretch;
+            }
+            "place-items-baseline" => {
+                self.align_items = AlignItems::Baseline;
+                self.justify_items = JustifyItems::Baseline;
+            }
+
+            "place-self-auto" => {
+                self.align_self = AlignSelf::Auto;
+                self.justify_self = JustifySelf::Auto;
+            }
+            "place-self-start" => {
+                self.align_self = AlignSelf::FlexStart;
+                self.justify_self = JustifySelf::Start;
+            }
+            "place-self-end" => {
+                self.align_self = AlignSelf::FlexEnd;
+                self.justify_self = JustifySelf::End;
+            }
+            "place-self-center" => {
+                self.align_self = AlignSelf::Center;
+                self.justify_self = JustifySelf::Center;
+            }
+            "place-self-stretch" => {
+                self.align_self = AlignSelf::Stretch;
+                self.justify_self = JustifySelf::Stretch;
+            }
+
+            "w-full" => self.width = percent(100.0),
+            "h-full" => self.height = percent(100.0),
+            "size-full" => {
+                self.width = percent(100);
+                self.height = percent(100.0);
+            }
+
+            "border" => self.border = UiRect::all(px(1)),
+            "border-x" => {
+                self.border = UiRect {
+                    left: px(1.0),
+                    right: px(1.0),
+                    ..self.border
                 }
-                "border-t" => {
-                    style.border = UiRect {
-                        top: px(1.0),
-                        ..style.border
-                    }
+            }
+            "border-y" => {
+                self.border = UiRect {
+                    top: px(1.0),
+                    bottom: px(1.0),
+                    ..self.border
                 }
-
-                "p-px" => style.padding = UiRect::all(px(1.0)),
-                "p-auto" => style.padding = UiRect::all(auto()),
-                "px-auto" => {
-                    style.padding = UiRect {
-                        left: auto(),
-                        right: auto(),
-                        ..style.padding
-                    }
+            }
+            "border-l" => {
+                self.border = UiRect {
+                    left: px(1.0),
+                    ..self.border
                 }
-                "py-auto" => {
-                    style.padding = UiRect {
-                        top: auto(),
-                        bottom: auto(),
-                        ..style.padding
-                    }
+            }
+            "border-r" => {
+                self.border = UiRect {
+                    right: px(1.0),
+                    ..self.border
                 }
-                "m-px" => style.margin = UiRect::all(px(1.0)),
-                "m-auto" => style.margin = UiRect::all(auto()),
-                "mx-auto" => {
-                    style.margin = UiRect {
-                        left: auto(),
-                        right: auto(),
-                        ..style.margin
-                    }
+            }
+            "border-b" => {
+                self.border = UiRect {
+                    bottom: px(1.0),
+                    ..self.border
                 }
-                "my-auto" => {
-                    style.margin = UiRect {
-                        top: auto(),
-                        bottom: auto(),
-                        ..style.margin
-                    }
+            }
+            "border-t" => {
+                self.border = UiRect {
+                    top: px(1.0),
+                    ..self.border
                 }
+            }
 
-                "text-white" => style.text_color = TextColor::WHITE,
-                "text-black" => style.text_color = TextColor::BLACK,
+            "outline" => self.outline_width = px(1),
+            "outline-none" => self.outline_width = px(0),
 
-                "text-left" => {
-                    style.text_layout = TextLayout {
-                        justify: Justify::Left,
-                        ..style.text_layout
-                    }
-                }
-                "text-right" => {
-                    style.text_layout = TextLayout {
-                        justify: Justify::Right,
-                        ..style.text_layout
-                    }
-                }
-                "text-center" => {
-                    style.text_layout = TextLayout {
-                        justify: Justify::Center,
-                        ..style.text_layout
-                    }
+            "p-px" => self.padding = UiRect::all(px(1.0)),
+            "p-auto" => self.padding = UiRect::all(auto()),
+            "px-auto" => {
+                self.padding = UiRect {
+                    left: auto(),
+                    right: auto(),
+                    ..self.padding
                 }
-                "text-justify" => {
-                    style.text_layout = TextLayout {
-                        justify: Justify::Justified,
-                        ..style.text_layout
-                    }
+            }
+            "py-auto" => {
+                self.padding = UiRect {
+                    top: auto(),
+                    bottom: auto(),
+                    ..self.padding
                 }
-                "text-wrap" => {
-                    style.text_layout = TextLayout {
-                        linebreak: LineBreak::WordBoundary,
-                        ..style.text_layout
-                    }
+            }
+            "m-px" => self.margin = UiRect::all(px(1.0)),
+            "m-auto" => self.margin = UiRect::all(auto()),
+            "mx-auto" => {
+                self.margin = UiRect {
+                    left: auto(),
+                    right: auto(),
+                    ..self.margin
                 }
-                "text-nowrap" => {
-                    style.text_layout = TextLayout {
-                        linebreak: LineBreak::NoWrap,
-                        ..style.text_layout
-                    }
+            }
+            "my-auto" => {
+                self.margin = UiRect {
+                    top: auto(),
+                    bottom: auto(),
+                    ..self.margin
                 }
-                "break-all" => {
-                    style.text_layout = TextLayout {
-                        linebreak: LineBreak::AnyCharacter,
-                        ..style.text_layout
-                    }
+            }
+
+            "text-left" => {
+                self.text_layout = TextLayout {
+                    justify: Justify::Left,
+                    ..self.text_layout
                 }
-                "text-xs" => {
-                    style.text_font = TextFont {
-                        font_size: 12.0,
-                        ..style.text_font
-                    };
-                    style.line_height = LineHeight::RelativeToFont(1.0 / 0.75);
+            }
+            "text-right" => {
+                self.text_layout = TextLayout {
+                    justify: Justify::Right,
+                    ..self.text_layout
                 }
-                "text-sm" => {
-                    style.text_font = TextFont {
-                        font_size: 14.0,
-                        ..style.text_font
-                    };
-                    style.line_height = LineHeight::RelativeToFont(1.25 / 0.875);
+            }
+            "text-center" => {
+                self.text_layout = TextLayout {
+                    justify: Justify::Center,
+                    ..self.text_layout
                 }
-                "text-base" => {
-                    style.text_font = TextFont {
-                        font_size: 16.0,
-                        ..style.text_font
-                    };
-                    style.line_height = LineHeight::RelativeToFont(1.5 / 1.0);
+            }
+            "text-justify" => {
+                self.text_layout = TextLayout {
+                    justify: Justify::Justified,
+                    ..self.text_layout
                 }
-                "text-lg" => {
-                    style.text_font = TextFont {
-                        font_size: 18.0,
-                        ..style.text_font
-                    };
-                    style.line_height = LineHeight::RelativeToFont(1.75 / 1.125);
+            }
+            "text-wrap" => {
+                self.text_layout = TextLayout {
+                    linebreak: LineBreak::WordBoundary,
+                    ..self.text_layout
                 }
-                "text-xl" => {
-                    style.text_font = TextFont {
-                        font_size: 20.0,
-                        ..style.text_font
-                    };
-                    style.line_height = LineHeight::RelativeToFont(1.75 / 1.25);
+            }
+            "text-nowrap" => {
+                self.text_layout = TextLayout {
+                    linebreak: LineBreak::NoWrap,
+                    ..self.text_layout
                 }
-                "text-2xl" => {
-                    style.text_font = TextFont {
-                        font_size: 24.0,
-                        ..style.text_font
-                    };
-                    style.line_height = LineHeight::RelativeToFont(2.0 / 1.5);
+            }
+            "break-all" => {
+                self.text_layout = TextLayout {
+                    linebreak: LineBreak::AnyCharacter,
+                    ..self.text_layout
                 }
-                "text-3xl" => {
-                    style.text_font = TextFont {
-                        font_size: 30.0,
-                        ..style.text_font
+            }
+
+            "font-thin" => self.text_font.weight = FontWeight::THIN,
+            "font-extralight" => self.text_font.weight = FontWeight::EXTRA_LIGHT,
+            "font-light" => self.text_font.weight = FontWeight::LIGHT,
+            "font-normal" => self.text_font.weight = FontWeight::NORMAL,
+            "font-medium" => self.text_font.weight = FontWeight::MEDIUM,
+            "font-semibold" => self.text_font.weight = FontWeight::SEMIBOLD,
+            "font-bold" => self.text_font.weight = FontWeight::BOLD,
+            "font-extrabold" => self.text_font.weight = FontWeight::EXTRA_BOLD,
+            "font-black" => self.text_font.weight = FontWeight::BLACK,
+
+            "italic" => self.italic = true,
+            "not-italic" => self.italic = false,
+
+            "underline" => self.underline = true,
+            "line-through" => self.strikethrough = true,
+            "no-underline" => {
+                self.underline = false;
+                self.strikethrough = false;
+            }
+
+            "antialiased" => self.text_font.font_smoothing = FontSmoothing::AntiAliased,
+
+            _ => {
+                if let Some(font_size) = class
+                    .strip_prefix("text-")
+                    .and_then(|name| ctx.theme.font_sizes.get(name))
+                {
+                    self.text_font.font_size = font_size.size;
+                    if let Some(line_height) = font_size.line_height {
+                        self.line_height = LineHeight::RelativeToFont(line_height);
+                    }
+                } else if let Some((utility, value)) = spacing_class(class, ctx) {
+                    self.apply_spacing(utility, value);
+                } else if let Some((utility, color)) = color_class(class, &ctx.theme) {
+                    self.apply_color(utility, color);
+                } else if let Some((utility, radius)) = radius_class(class, ctx) {
+                    self.apply_radius(utility, radius);
+                } else if let Some(width) = REGEX
+                    .outline_width
+                    .captures(class)
+                    .and_then(|captures| captures_length(&captures, ctx.rem))
+                {
+                    self.outline_width = width;
+                } else if let Some(offset) = REGEX
+                    .outline_offset
+                    .captures(class)
+                    .and_then(|captures| captures_length(&captures, ctx.rem))
+                {
+                    self.outline_offset = offset;
+                } else if let Some(width) = REGEX
+                    .divide_x
+                    .captures(class)
+                    .and_then(|captures| divider_width(&captures, ctx.rem))
+                {
+                    self.divide_x = Some(width);
+                } else if let Some(width) = REGEX
+                    .divide_y
+                    .captures(class)
+                    .and_then(|captures| divider_width(&captures, ctx.rem))
+                {
+                    self.divide_y = Some(width);
+                } else if let Some(width) = REGEX
+                    .border
+                    .captures(class)
+                    .and_then(|c| c[1].parse::<u64>().ok())
+                {
+                    self.border = UiRect::all(px(width));
+                } else if let Some(width) = REGEX
+                    .border_x
+                    .captures(class)
+                    .and_then(|c| c[1].parse::<u64>().ok())
+                {
+                    self.border = UiRect {
+                        left: px(width),
+                        right: px(width),
+                        ..self.border
                     };
-                    style.line_height = LineHeight::RelativeToFont(2.25 / 1.875);
-                }
-                "text-4xl" => {
-                    style.text_font = TextFont {
-                        font_size: 36.0,
-                        ..style.text_font
+                } else if let Some(width) = REGEX
+                    .border_y
+                    .captures(class)
+                    .and_then(|c| c[1].parse::<u64>().ok())
+                {
+                    self.border = UiRect {
+                        top: px(width),
+                        bottom: px(width),
+                        ..self.border
                     };
-                    style.line_height = LineHeight::RelativeToFont(2.5 / 2.25);
-                }
-                "text-5xl" => {
-                    style.text_font = TextFont {
-                        font_size: 48.0,
-                        ..style.text_font
+                } else if let Some(width) = REGEX
+                    .border_t
+                    .captures(class)
+                    .and_then(|c| c[1].parse::<u64>().ok())
+                {
+                    self.border = UiRect {
+                        top: px(width),
+                        ..self.border
                     };
-                    style.line_height = LineHeight::RelativeToFont(1.0);
-                }
-                "text-6xl" => {
-                    style.text_font = TextFont {
-                        font_size: 60.0,
-                        ..style.text_font
+                } else if let Some(width) = REGEX
+                    .border_b
+                    .captures(class)
+                    .and_then(|c| c[1].parse::<u64>().ok())
+                {
+                    self.border = UiRect {
+                        bottom: px(width),
+                        ..self.border
                     };
-                    style.line_height = LineHeight::RelativeToFont(1.0);
-                }
-                "text-7xl" => {
-                    style.text_font = TextFont {
-                        font_size: 72.0,
-                        ..style.text_font
+                } else if let Some(width) = REGEX
+                    .border_l
+                    .captures(class)
+                    .and_then(|c| c[1].parse::<u64>().ok())
+                {
+                    self.border = UiRect {
+                        left: px(width),
+                        ..self.border
                     };
-                    style.line_height = LineHeight::RelativeToFont(1.0);
-                }
-                "text-8xl" => {
-                    style.text_font = TextFont {
-                        font_size: 96.0,
-                        ..style.text_font
+                } else if let Some(width) = REGEX
+                    .border_r
+                    .captures(class)
+                    .and_then(|c| c[1].parse::<u64>().ok())
+                {
+                    self.border = UiRect {
+                        right: px(width),
+                        ..self.border
                     };
-                    style.line_height = LineHeight::RelativeToFont(1.0);
-                }
-                "text-9xl" => {
-                    style.text_font = TextFont {
-                        font_size: 128.0,
-                        ..style.text_font
+                } else if let Some(captures) = REGEX.z_index.captures(class)
+                    && let Ok(index) = captures[2].parse::<i32>()
+                {
+                    let negative = captures.get(1).is_some();
+                    self.z_index = ZIndex(if negative { -index } else { index });
+                } else if let Some(columns) = REGEX
+                    .grid_template_columns
+                    .captures(class)
+                    .and_then(|captures| captures_grid_tracks(&captures, ctx.rem))
+                {
+                    self.grid_template_columns = columns;
+                } else if let Some(rows) = REGEX
+                    .grid_template_rows
+                    .captures(class)
+                    .and_then(|captures| captures_grid_tracks(&captures, ctx.rem))
+                {
+                    self.grid_template_rows = rows;
+                } else if let Some(span) = REGEX
+                    .col_span
+                    .captures(class)
+                    .and_then(|c| c[1].parse::<u16>().ok())
+                    .filter(|span| *span > 0)
+                {
+                    self.grid_column = GridPlacement::span(span);
+                } else if let Some(span) = REGEX
+                    .row_span
+                    .captures(class)
+                    .and_then(|c| c[1].parse::<u16>().ok())
+                    .filter(|span| *span > 0)
+                {
+                    self.grid_row = GridPlacement::span(span);
+                } else if REGEX.custom_font.is_match(class) {
+                    let Some(captures) = REGEX.custom_font.captures(class) else {
+                        return Ok(());
                     };
-                    style.line_height = LineHeight::RelativeToFont(1.0);
-                }
 
-                "rounded-xs" => style.border_radius = BorderRadius::all(px(2)),
-                "rounded-sm" => style.border_radius = BorderRadius::all(px(4)),
-                "rounded-md" => style.border_radius = BorderRadius::all(px(6)),
-                "rounded-lg" => style.border_radius = BorderRadius::all(px(8)),
-                "rounded-xl" => style.border_radius = BorderRadius::all(px(12)),
-                "rounded-2xl" => style.border_radius = BorderRadius::all(px(16)),
-                "rounded-3xl" => style.border_radius = BorderRadius::all(px(24)),
-                "rounded-4xl" => style.border_radius = BorderRadius::all(px(32)),
-                "rounded-full" => style.border_radius = BorderRadius::MAX,
-                "rounded-tl-xs" => style.border_radius = style.border_radius.with_top_left(px(2)),
-                "rounded-tr-xs" => style.border_radius = style.border_radius.with_top_right(px(2)),
-                "rounded-bl-xs" => {
-                    style.border_radius = style.border_radius.with_bottom_left(px(2))
-                }
-                "rounded-br-xs" => {
-                    style.border_radius = style.border_radius.with_bottom_right(px(2))
-                }
-                "rounded-tl-sm" => style.border_radius = style.border_radius.with_top_left(px(4)),
-                "rounded-tr-sm" => style.border_radius = style.border_radius.with_top_right(px(4)),
-                "rounded-bl-sm" => {
-                    style.border_radius = style.border_radius.with_bottom_left(px(4))
-                }
-                "rounded-br-sm" => {
-                    style.border_radius = style.border_radius.with_bottom_right(px(4))
-                }
-                "rounded-tl-md" => style.border_radius = style.border_radius.with_top_left(px(6)),
-                "rounded-tr-md" => style.border_radius = style.border_radius.with_top_right(px(6)),
-                "rounded-bl-md" => {
-                    style.border_radius = style.border_radius.with_bottom_left(px(6))
-                }
-                "rounded-br-md" => {
-                    style.border_radius = style.border_radius.with_bottom_right(px(6))
-                }
-                "rounded-tl-lg" => style.border_radius = style.border_radius.with_top_left(px(8)),
-                "rounded-tr-lg" => style.border_radius = style.border_radius.with_top_right(px(8)),
-                "rounded-bl-lg" => {
-                    style.border_radius = style.border_radius.with_bottom_left(px(8))
-                }
-                "rounded-br-lg" => {
-                    style.border_radius = style.border_radius.with_bottom_right(px(8))
-                }
-                "rounded-tl-xl" => style.border_radius = style.border_radius.with_top_left(px(12)),
-                "rounded-tr-xl" => style.border_radius = style.border_radius.with_top_right(px(12)),
-                "rounded-bl-xl" => {
-                    style.border_radius = style.border_radius.with_bottom_left(px(12))
-                }
-                "rounded-br-xl" => {
-                    style.border_radius = style.border_radius.with_bottom_right(px(12))
-                }
-                "rounded-tl-2xl" => style.border_radius = style.border_radius.with_top_left(px(16)),
-                "rounded-tr-2xl" => {
-                    style.border_radius = style.border_radius.with_top_right(px(16))
-                }
-                "rounded-bl-2xl" => {
-                    style.border_radius = style.border_radius.with_bottom_left(px(16))
-                }
-                "rounded-br-2xl" => {
-                    style.border_radius = style.border_radius.with_bottom_right(px(16))
-                }
-                "rounded-tl-3xl" => style.border_radius = style.border_radius.with_top_left(px(24)),
-                "rounded-tr-3xl" => {
-                    style.border_radius = style.border_radius.with_top_right(px(24))
-                }
-                "rounded-bl-3xl" => {
-                    style.border_radius = style.border_radius.with_bottom_left(px(24))
+                    let font_name = captures.get(1).unwrap().as_str();
+                    if !ctx.fonts.contains_key(font_name) {
+                        return Err(StyleError::UnknownFont(font_name.to_string()));
+                    }
+                    self.font_family = Some(font_name.to_string());
+                } else if let Some(font_size) = REGEX
+                    .font_size
+                    .captures(class)
+                    .and_then(|c| font_size(&c[1], ctx.rem, self.text_font.font_size))
+                {
+                    self.text_font.font_size = font_size;
+                } else if let Some(radius) = REGEX
+                    .border_radius
+                    .captures(class)
+                    .and_then(|c| arbitrary_length(&c[1], ctx.rem))
+                {
+                    self.border_radius = BorderRadius::all(radius);
+                } else if let Some(radius) = REGEX
+                    .border_radius_bl
+                    .captures(class)
+                    .and_then(|c| arbitrary_length(&c[1], ctx.rem))
+                {
+                    self.border_radius = self.border_radius.with_bottom_left(radius);
+                } else if let Some(radius) = REGEX
+                    .border_radius_br
+                    .captures(class)
+                    .and_then(|c| arbitrary_length(&c[1], ctx.rem))
+                {
+                    self.border_radius = self.border_radius.with_bottom_right(radius);
+                } else if let Some(radius) = REGEX
+                    .border_radius_tl
+                    .captures(class)
+                    .and_then(|c| arbitrary_length(&c[1], ctx.rem))
+                {
+                    self.border_radius = self.border_radius.with_top_left(radius);
+                } else if let Some(radius) = REGEX
+                    .border_radius_tr
+                    .captures(class)
+                    .and_then(|c| arbitrary_length(&c[1], ctx.rem))
+                {
+                    self.border_radius = self.border_radius.with_top_right(radius);
+                } else if let Some(opacity) = class.strip_prefix("opacity-").and_then(opacity) {
+                    self.opacity = opacity;
+                } else if let Some(captures) = REGEX.scale.captures(class) {
+                    let scale = captures[2].parse::<f32>().unwrap_or(100.0) / 100.0;
+                    match captures.get(1).map(|axis| axis.as_str()) {
+                        Some("x") => self.scale.x = scale,
+                        Some("y") => self.scale.y = scale,
+                        _ => self.scale = Vec2::splat(scale),
+                    }
+                } else {
+                    return Err(StyleError::UnsupportedClass(class.to_string()));
                 }
-                "rounded-br-3xl" => {
-                    style.border_radius = style.border_radius.with_bottom_right(px(24))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set the value of a utility of the spacing scale, see [`spacing_class`]
+    fn apply_spacing(&mut self, utility: &str, value: Val) {
+        match utility {
+            "p" => self.padding = UiRect::all(value),
+            "px" => {
+                self.padding = UiRect {
+                    left: value,
+                    right: value,
+                    ..self.padding
                 }
-                "rounded-tl-4xl" => style.border_radius = style.border_radius.with_top_left(px(32)),
-                "rounded-tr-4xl" => {
-                    style.border_radius = style.border_radius.with_top_right(px(32))
+            }
+            "py" => {
+                self.padding = UiRect {
+                    top: value,
+                    bottom: value,
+                    ..self.padding
                 }
-                "rounded-bl-4xl" => {
-                    style.border_radius = style.border_radius.with_bottom_left(px(32))
+            }
+            "pt" => self.padding.top = value,
+            "pr" => self.padding.right = value,
+            "pb" => self.padding.bottom = value,
+            "pl" => self.padding.left = value,
+            "m" => self.margin = UiRect::all(value),
+            "mx" => {
+                self.margin = UiRect {
+                    left: value,
+                    right: value,
+                    ..self.margin
                 }
-                "rounded-br-4xl" => {
-                    style.border_radius = style.border_radius.with_bottom_right(px(32))
+            }
+            "my" => {
+                self.margin = UiRect {
+                    top: value,
+                    bottom: value,
+                    ..self.margin
                 }
+            }
+            "mt" => self.margin.top = value,
+            "mr" => self.margin.right = value,
+            "mb" => self.margin.bottom = value,
+            "ml" => self.margin.left = value,
+            "gap" => {
+                self.row_gap = value;
+                self.column_gap = value;
+            }
+            "gap-x" => self.column_gap = value,
+            "gap-y" => self.row_gap = value,
+            "inset" => {
+                self.top = value;
+                self.right = value;
+                self.bottom = value;
+                self.left = value;
+            }
+            "inset-x" => {
+                self.left = value;
+                self.right = value;
+            }
+            "inset-y" => {
+                self.top = value;
+                self.bottom = value;
+            }
+            "top" => self.top = value,
+            "right" => self.right = value,
+            "bottom" => self.bottom = value,
+            "left" => self.left = value,
+            "w" => self.width = value,
+            "min-w" => self.min_width = value,
+            "max-w" => self.max_width = value,
+            "h" => self.height = value,
+            "min-h" => self.min_height = value,
+            "max-h" => self.max_height = value,
+            "size" => {
+                self.width = value;
+                self.height = value;
+            }
+            "basis" => self.flex_basis = value,
+            "space-x" => self.space_x = Some(value),
+            "space-y" => self.space_y = Some(value),
+            _ => {}
+        }
+    }
 
-                "antialiased" => {
-                    style.text_font = style
-                        .text_font
-                        .with_font_smoothing(FontSmoothing::AntiAliased)
-                }
+    /// Set the radius of a border radius utility, see [`radius_class`]
+    fn apply_radius(&mut self, utility: &str, radius: Val) {
+        self.border_radius = match utility {
+            "rounded" => BorderRadius::all(radius),
+            "rounded-t" => self.border_radius.with_top(radius),
+            "rounded-r" => self.border_radius.with_right(radius),
+            "rounded-b" => self.border_radius.with_bottom(radius),
+            "rounded-l" => self.border_radius.with_left(radius),
+            "rounded-tl" => self.border_radius.with_top_left(radius),
+            "rounded-tr" => self.border_radius.with_top_right(radius),
+            "rounded-br" => self.border_radius.with_bottom_right(radius),
+            "rounded-bl" => self.border_radius.with_bottom_left(radius),
+            _ => self.border_radius,
+        };
+    }
 
-                _ => {
-                    if REGEX.width.is_match(class) {
-                        let Some(captures) = REGEX.width.captures(class) else {
-                            continue;
-                        };
-                        if let Some(px_val) =
-                            captures.get(1).map(|x| x.as_str().parse::<u64>().unwrap())
-                        {
-                            style.width = px(px_val);
-                        }
-
-                        if let Some(percent_val) =
-                            captures.get(2).map(|x| x.as_str().parse::<u64>().unwrap())
-                        {
-                            style.width = percent(percent_val);
-                        }
-                    } else if REGEX.min_width.is_match(class) {
-                        let Some(captures) = REGEX.min_width.captures(class) else {
-                            continue;
-                        };
-
-                        if let Some(px_val) =
-                            captures.get(1).map(|x| x.as_str().parse::<u64>().unwrap())
-                        {
-                            style.min_width = px(px_val);
-                        }
-
-                        if let Some(percent_val) =
-                            captures.get(2).map(|x| x.as_str().parse::<u64>().unwrap())
-                        {
-                            style.min_width = percent(percent_val);
-                        }
-                    } else if REGEX.max_width.is_match(class) {
-                        let Some(captures) = REGEX.max_width.captures(class) else {
-                            continue;
-                        };
-
-                        if let Some(px_val) =
-                            captures.get(1).map(|x| x.as_str().parse::<u64>().unwrap())
-                        {
-                            style.max_width = px(px_val);
-                        }
-
-                        if let Some(percent_val) =
-                            captures.get(2).map(|x| x.as_str().parse::<u64>().unwrap())
-                        {
-                            style.max_width = percent(percent_val);
-                        }
-                    } else if REGEX.height.is_match(class) {
-                        let Some(captures) = REGEX.height.captures(class) else {
-                            continue;
-                        };
-
-                        if let Some(px_val) =
-                            captures.get(1).map(|x| x.as_str().parse::<u64>().unwrap())
-                        {
-                            style.height = px(px_val);
-                        }
-
-                        if let Some(percent_val) =
-                            captures.get(2).map(|x| x.as_str().parse::<u64>().unwrap())
-                        {
-                            style.height = percent(percent_val);
-                        }
-                    } else if REGEX.min_height.is_match(class) {
-                        let Some(captures) = REGEX.min_height.captures(class) else {
-                            continue;
-                        };
-
-                        if let Some(px_val) =
-                            captures.get(1).map(|x| x.as_str().parse::<u64>().unwrap())
-                        {
-                            style.min_height = px(px_val);
-                        }
-
-                        if let Some(percent_val) =
-                            captures.get(2).map(|x| x.as_str().parse::<u64>().unwrap())
-                        {
-                            style.min_height = percent(percent_val);
-                        }
-                    } else if REGEX.max_height.is_match(class) {
-                        let Some(captures) = REGEX.max_height.captures(class) else {
-                            continue;
-                        };
-
-                        if let Some(px_val) =
-                            captures.get(1).map(|x| x.as_str().parse::<u64>().unwrap())
-                        {
-                            style.max_height = px(px_val);
-                        }
-
-                        if let Some(percent_val) =
-                            captures.get(2).map(|x| x.as_str().parse::<u64>().unwrap())
-                        {
-                            style.max_height = percent(percent_val);
-                        }
-                    } else if REGEX.border.is_match(class) {
-                        let Some(captures) = REGEX.border.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.border = UiRect::all(px(px_val));
-                    } else if REGEX.border_x.is_match(class) {
-                        let Some(captures) = REGEX.border_x.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.border = UiRect {
-                            left: px(px_val),
-                            right: px(px_val),
-                            ..style.border
-                        };
-                    } else if REGEX.border_y.is_match(class) {
-                        let Some(captures) = REGEX.border_y.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.border = UiRect {
-                            top: px(px_val),
-                            bottom: px(px_val),
-                            ..style.border
-                        };
-                    } else if REGEX.border_t.is_match(class) {
-                        let Some(captures) = REGEX.border_t.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.border = UiRect {
-                            top: px(px_val),
-                            ..style.border
-                        };
-                    } else if REGEX.border_b.is_match(class) {
-                        let Some(captures) = REGEX.border_b.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.border = UiRect {
-                            bottom: px(px_val),
-                            ..style.border
-                        };
-                    } else if REGEX.border_l.is_match(class) {
-                        let Some(captures) = REGEX.border_l.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.border = UiRect {
-                            left: px(px_val),
-                            ..style.border
-                        };
-                    } else if REGEX.border_r.is_match(class) {
-                        let Some(captures) = REGEX.border_r.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.border = UiRect {
-                            right: px(px_val),
-                            ..style.border
-                        };
-                    } else if REGEX.border_color.is_match(class) {
-                        let Some(captures) = REGEX.border_color.captures(class) else {
-                            continue;
-                        };
-                        let color_r =
-                            u8::from_str_radix(captures.get(1).unwrap().as_str(), 16).unwrap();
-                        let color_g =
-                            u8::from_str_radix(captures.get(2).unwrap().as_str(), 16).unwrap();
-                        let color_b =
-                            u8::from_str_radix(captures.get(3).unwrap().as_str(), 16).unwrap();
-                        let color_a = captures
-                            .get(4)
-                            .map(|x| u8::from_str_radix(x.as_str(), 16).unwrap())
-                            .unwrap_or(255);
-
-                        style.border_color =
-                            BorderColor::all(Color::srgba_u8(color_r, color_g, color_b, color_a));
-                    } else if REGEX.background_color.is_match(class) {
-                        let Some(captures) = REGEX.background_color.captures(class) else {
-                            continue;
-                        };
-                        let color_r =
-                            u8::from_str_radix(captures.get(1).unwrap().as_str(), 16).unwrap();
-                        let color_g =
-                            u8::from_str_radix(captures.get(2).unwrap().as_str(), 16).unwrap();
-                        let color_b =
-                            u8::from_str_radix(captures.get(3).unwrap().as_str(), 16).unwrap();
-                        let color_a = captures
-                            .get(4)
-                            .map(|x| u8::from_str_radix(x.as_str(), 16).unwrap())
-                            .unwrap_or(255);
-
-                        style.background_color =
-                            BackgroundColor(Color::srgba_u8(color_r, color_g, color_b, color_a));
-                    } else if REGEX.padding.is_match(class) {
-                        let Some(captures) = REGEX.padding.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.padding = UiRect::all(px(px_val));
-                    } else if REGEX.padding_x.is_match(class) {
-                        let Some(captures) = REGEX.padding_x.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.padding = UiRect {
-                            left: px(px_val),
-                            right: px(px_val),
-                            ..style.padding
-                        };
-                    } else if REGEX.padding_y.is_match(class) {
-                        let Some(captures) = REGEX.padding_y.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.padding = UiRect {
-                            top: px(px_val),
-                            bottom: px(px_val),
-                            ..style.padding
-                        };
-                    } else if REGEX.padding_l.is_match(class) {
-                        let Some(captures) = REGEX.padding_l.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.padding = UiRect {
-                            left: px(px_val),
-                            ..style.padding
-                        };
-                    } else if REGEX.padding_r.is_match(class) {
-                        let Some(captures) = REGEX.padding_r.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.padding = UiRect {
-                            right: px(px_val),
-                            ..style.padding
-                        };
-                    } else if REGEX.padding_b.is_match(class) {
-                        let Some(captures) = REGEX.padding_b.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.padding = UiRect {
-                            bottom: px(px_val),
-                            ..style.padding
-                        };
-                    } else if REGEX.padding_t.is_match(class) {
-                        let Some(captures) = REGEX.padding_t.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.padding = UiRect {
-                            top: px(px_val),
-                            ..style.padding
-                        };
-                    } else if REGEX.margin.is_match(class) {
-                        let Some(captures) = REGEX.margin.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.margin = UiRect::all(px(px_val));
-                    } else if REGEX.margin_x.is_match(class) {
-                        let Some(captures) = REGEX.margin_x.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.margin = UiRect {
-                            left: px(px_val),
-                            right: px(px_val),
-                            ..style.margin
-                        };
-                    } else if REGEX.margin_y.is_match(class) {
-                        let Some(captures) = REGEX.margin_y.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.margin = UiRect {
-                            top: px(px_val),
-                            bottom: px(px_val),
-                            ..style.margin
-                        };
-                    } else if REGEX.margin_l.is_match(class) {
-                        let Some(captures) = REGEX.margin_l.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.margin = UiRect {
-                            left: px(px_val),
-                            ..style.margin
-                        };
-                    } else if REGEX.margin_r.is_match(class) {
-                        let Some(captures) = REGEX.margin_r.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.margin = UiRect {
-                            right: px(px_val),
-                            ..style.margin
-                        };
-                    } else if REGEX.margin_b.is_match(class) {
-                        let Some(captures) = REGEX.margin_b.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.margin = UiRect {
-                            bottom: px(px_val),
-                            ..style.margin
-                        };
-                    } else if REGEX.margin_t.is_match(class) {
-                        let Some(captures) = REGEX.margin_t.captures(class) else {
-                            continue;
-                        };
-                        let px_val = captures.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        style.margin = UiRect {
-                            top: px(px_val),
-                            ..style.margin
-                        };
-                    } else if REGEX.text_color.is_match(class) {
-                        let Some(captures) = REGEX.text_color.captures(class) else {
-                            continue;
-                        };
-                        let color_r =
-                            u8::from_str_radix(captures.get(1).unwrap().as_str(), 16).unwrap();
-                        let color_g =
-                            u8::from_str_radix(captures.get(2).unwrap().as_str(), 16).unwrap();
-                        let color_b =
-                            u8::from_str_radix(captures.get(3).unwrap().as_str(), 16).unwrap();
-                        let color_a = captures
-                            .get(4)
-                            .map(|x| u8::from_str_radix(x.as_str(), 16).unwrap())
-                            .unwrap_or(255);
-
-                        style.text_color =
-                            TextColor(Color::srgba_u8(color_r, color_g, color_b, color_a));
-                    } else if REGEX.z_index.is_match(class) {
-                        let Some(captures) = REGEX.z_index.captures(class) else {
-                            continue;
-                        };
-
-                        let negative = captures.get(1).map(|x| x.as_str() == "-").unwrap_or(false);
-                        let index = captures.get(2).unwrap().as_str().parse::<usize>().unwrap();
-
-                        style.z_index = ZIndex(if negative {
-                            -1 * index as i32
-                        } else {
-                            index as i32
-                        });
-                    } else if REGEX.grid_template_columns.is_match(class) {
-                        let Some(captures) = REGEX.grid_template_columns.captures(class) else {
-                            continue;
-                        };
-
-                        if let Some(c) = captures.get(1) {
-                            let columns = c.as_str().parse::<u16>().unwrap();
-                            style.grid_template_columns = RepeatedGridTrack::auto(columns);
-                            continue;
-                        }
-
-                        let Some(matches) = captures.get(2).map(|x| x.as_str()) else {
-                            continue;
-                        };
-
-                        let mut columns = vec![];
-                        for part in matches.split('_') {
-                            if part == "auto" {
-                                columns.push(RepeatedGridTrack::auto(1));
-                            } else if part.ends_with("fr") {
-                                let count = part.trim_end_matches("fr").parse::<u16>().unwrap();
-                                columns.push(RepeatedGridTrack::fr(1, count as f32));
-                            } else if part.ends_with("px") {
-                                let count = part.trim_end_matches("px").parse::<u16>().unwrap();
-                                columns.push(RepeatedGridTrack::px(1, count as f32));
-                            }
-                        }
-                        style.grid_template_columns = columns;
-                    } else if REGEX.grid_template_rows.is_match(class) {
-                        let Some(captures) = REGEX.grid_template_rows.captures(class) else {
-                            continue;
-                        };
-
-                        if let Some(c) = captures.get(1) {
-                            let rows = c.as_str().parse::<u16>().unwrap();
-                            style.grid_template_rows = RepeatedGridTrack::auto(rows);
-                            continue;
-                        }
-
-                        let Some(matches) = captures.get(2).map(|x| x.as_str()) else {
-                            continue;
-                        };
-
-                        let mut rows = vec![];
-                        for part in matches.split('_') {
-                            if part == "auto" {
-                                rows.push(RepeatedGridTrack::auto(1));
-                            } else if part.ends_with("fr") {
-                                let count = part.trim_end_matches("fr").parse::<u16>().unwrap();
-                                rows.push(RepeatedGridTrack::fr(1, count as f32));
-                            } else if part.ends_with("px") {
-                                let count = part.trim_end_matches("px").parse::<u16>().unwrap();
-                                rows.push(RepeatedGridTrack::px(1, count as f32));
-                            }
-                        }
-                        style.grid_template_rows = rows;
-                    } else if REGEX.row_gap.is_match(class) {
-                        let Some(captures) = REGEX.row_gap.captures(class) else {
-                            continue;
-                        };
-
-                        let row_gap = captures.get(1).unwrap().as_str().parse::<usize>().unwrap();
-
-                        style.row_gap = px(row_gap);
-                    } else if REGEX.column_gap.is_match(class) {
-                        let Some(captures) = REGEX.column_gap.captures(class) else {
-                            continue;
-                        };
-
-                        let column_gap =
-                            captures.get(1).unwrap().as_str().parse::<usize>().unwrap();
-
-                        style.column_gap = px(column_gap);
-                    } else if REGEX.gap.is_match(class) {
-                        let Some(captures) = REGEX.gap.captures(class) else {
-                            continue;
-                        };
-
-                        let gap = captures.get(1).unwrap().as_str().parse::<usize>().unwrap();
-
-                        style.column_gap = px(gap);
-                        style.row_gap = px(gap);
-                    } else if REGEX.left.is_match(class) {
-                        let Some(captures) = REGEX.left.captures(class) else {
-                            continue;
-                        };
-
-                        let negative = captures.get(1).map(|x| x.as_str() == "-").unwrap_or(false);
-                        let distance = captures.get(2).unwrap().as_str().parse::<isize>().unwrap();
-
-                        style.left = if negative {
-                            px(-1 * distance)
-                        } else {
-                            px(distance)
-                        };
-                    } else if REGEX.right.is_match(class) {
-                        let Some(captures) = REGEX.right.captures(class) else {
-                            continue;
-                        };
-
-                        let negative = captures.get(1).map(|x| x.as_str() == "-").unwrap_or(false);
-                        let distance = captures.get(2).unwrap().as_str().parse::<isize>().unwrap();
-
-                        style.right = if negative {
-                            px(-1 * distance)
-                        } else {
-                            px(distance)
-                        };
-                    } else if REGEX.bottom.is_match(class) {
-                        let Some(captures) = REGEX.bottom.captures(class) else {
-                            continue;
-                        };
-
-                        let negative = captures.get(1).map(|x| x.as_str() == "-").unwrap_or(false);
-                        let distance = captures.get(2).unwrap().as_str().parse::<isize>().unwrap();
-
-                        style.bottom = if negative {
-                            px(-1 * distance)
-                        } else {
-                            px(distance)
-                        };
-                    } else if REGEX.top.is_match(class) {
-                        let Some(captures) = REGEX.top.captures(class) else {
-                            continue;
-                        };
-
-                        let negative = captures.get(1).map(|x| x.as_str() == "-").unwrap_or(false);
-                        let distance = captures.get(2).unwrap().as_str().parse::<isize>().unwrap();
-
-                        style.top = if negative {
-                            px(-1 * distance)
-                        } else {
-                            px(distance)
-                        };
-                    } else if REGEX.col_span.is_match(class) {
-                        let Some(captures) = REGEX.col_span.captures(class) else {
-                            continue;
-                        };
-
-                        let span = captures.get(1).unwrap().as_str().parse::<u16>().unwrap();
-                        style.grid_column = GridPlacement::span(span);
-                    } else if REGEX.row_span.is_match(class) {
-                        let Some(captures) = REGEX.row_span.captures(class) else {
-                            continue;
-                        };
-
-                        let span = captures.get(1).unwrap().as_str().parse::<u16>().unwrap();
-                        style.grid_column = GridPlacement::span(span);
-                    } else if REGEX.custom_font.is_match(class) {
-                        let Some(captures) = REGEX.custom_font.captures(class) else {
-                            continue;
-                        };
-
-                        let font_name = captures.get(1).unwrap().as_str();
-                        style.text_font = TextFont {
-                            font: fonts
-                                .get(font_name)
-                                .expect(&format!("No font registered with name {font_name}"))
-                                .clone(),
-                            ..style.text_font
-                        };
-                    } else if REGEX.font_size.is_match(class) {
-                        let Some(captures) = REGEX.font_size.captures(class) else {
-                            continue;
-                        };
-
-                        let font_size = captures.get(1).unwrap().as_str().parse::<usize>().unwrap();
-                        style.text_font = TextFont {
-                            font_size: font_size as f32,
-                            ..style.text_font
-                        };
-                    } else if REGEX.border_radius.is_match(class) {
-                        let Some(captures) = REGEX.border_radius.captures(class) else {
-                            continue;
-                        };
-
-                        let border_radius =
-                            captures.get(1).unwrap().as_str().parse::<usize>().unwrap();
-                        style.border_radius = BorderRadius::all(px(border_radius));
-                    } else if REGEX.border_radius_bl.is_match(class) {
-                        let Some(captures) = REGEX.border_radius_bl.captures(class) else {
-                            continue;
-                        };
-
-                        let border_radius =
-                            captures.get(1).unwrap().as_str().parse::<usize>().unwrap();
-                        style.border_radius =
-                            style.border_radius.with_bottom_left(px(border_radius));
-                    } else if REGEX.border_radius_br.is_match(class) {
-                        let Some(captures) = REGEX.border_radius_br.captures(class) else {
-                            continue;
-                        };
-
-                        let border_radius =
-                            captures.get(1).unwrap().as_str().parse::<usize>().unwrap();
-                        style.border_radius =
-                            style.border_radius.with_bottom_right(px(border_radius));
-                    } else if REGEX.border_radius_tl.is_match(class) {
-                        let Some(captures) = REGEX.border_radius_tl.captures(class) else {
-                            continue;
-                        };
-
-                        let border_radius =
-                            captures.get(1).unwrap().as_str().parse::<usize>().unwrap();
-                        style.border_radius = style.border_radius.with_top_left(px(border_radius));
-                    } else if REGEX.border_radius_tr.is_match(class) {
-                        let Some(captures) = REGEX.border_radius_tr.captures(class) else {
-                            continue;
-                        };
-
-                        let border_radius =
-                            captures.get(1).unwrap().as_str().parse::<usize>().unwrap();
-                        style.border_radius = style.border_radius.with_top_right(px(border_radius));
-                    } else {
-                        warn!("Unsupported style class: {class}");
-                    }
+    /// Set the color of a color utility, see [`color_class`]
+    fn apply_color(&mut self, utility: &str, color: Color) {
+        match utility {
+            "bg" => self.background_color = BackgroundColor(color),
+            "text" => self.text_color = TextColor(color),
+            "border" => self.border_color = BorderColor::all(color),
+            "border-x" => {
+                self.border_color = BorderColor {
+                    left: color,
+                    right: color,
+                    ..self.border_color
+                }
+            }
+            "border-y" => {
+                self.border_color = BorderColor {
+                    top: color,
+                    bottom: color,
+                    ..self.border_color
                 }
             }
+            "border-t" => self.border_color.top = color,
+            "border-r" => self.border_color.right = color,
+            "border-b" => self.border_color.bottom = color,
+            "border-l" => self.border_color.left = color,
+            "outline" => self.outline_color = Some(color),
+            "divide" => self.divide_color = Some(color),
+            _ => {}
         }
+    }
 
-        style
+    /// Scale the alpha of a color by the opacity of the node and its ancestors
+    pub fn fade(&self, color: Color) -> Color {
+        let alpha = color.alpha() * self.opacity * self.inherited_opacity;
+        color.with_alpha(alpha)
     }
 
     pub fn to_node(&self) -> TailwindNodeBundle {
@@ -1288,16 +1616,432 @@ impl Style {
                 top: self.top,
                 left: self.left,
                 right: self.right,
+                flex_basis: self.flex_basis,
                 ..Default::default()
             },
             visibility: self.visibility,
-            border_color: self.border_color,
-            background_color: self.background_color,
-            text_color: self.text_color,
+            border_color: BorderColor {
+                top: self.fade(self.border_color.top),
+                right: self.fade(self.border_color.right),
+                bottom: self.fade(self.border_color.bottom),
+                left: self.fade(self.border_color.left),
+            },
+            background_color: BackgroundColor(self.fade(self.background_color.0)),
+            text_color: TextColor(self.fade(self.text_color.0)),
             z_index: self.z_index,
             text_layout: self.text_layout,
             text_font: self.text_font.clone(),
             line_height: self.line_height,
+            outline: Outline {
+                width: self.outline_width,
+                offset: self.outline_offset,
+                color: self.fade(self.outline_color.unwrap_or(self.text_color.0)),
+            },
+            transform: UiTransform {
+                scale: self.scale,
+                ..UiTransform::IDENTITY
+            },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::testing::context;
+
+    /// Resolve a spacing class with the default theme and a root font size of 16px
+    fn spacing(class: &str) -> Option<(&str, Val)> {
+        spacing_class(class, &context())
+    }
+
+    #[test]
+    fn spacing_scale() {
+        assert_eq!(spacing("p-4"), Some(("p", px(16))));
+        assert_eq!(spacing("m-0.5"), Some(("m", px(2))));
+        assert_eq!(spacing("gap-x-2"), Some(("gap-x", px(8))));
+        assert_eq!(spacing("p-px"), Some(("p", px(1))));
+        assert_eq!(spacing("p-0"), Some(("p", px(0))));
+        // Only multiples of a quarter step
+        assert_eq!(spacing("p-0.3"), None);
+        assert_eq!(spacing("p-inf"), None);
+        assert_eq!(spacing("p-NaN"), None);
+    }
+
+    #[test]
+    fn spacing_scale_follows_root_font_size() {
+        let ctx = StyleContext {
+            rem: 10.0,
+            ..context()
+        };
+        assert_eq!(spacing_class("p-4", &ctx), Some(("p", px(10))));
+        assert_eq!(spacing_class("p-[2rem]", &ctx), Some(("p", px(20))));
+    }
+
+    #[test]
+    fn negative_spacing() {
+        assert_eq!(spacing("-mt-2"), Some(("mt", px(-8))));
+        assert_eq!(spacing("-top-1/2"), Some(("top", percent(-50))));
+        assert_eq!(spacing("-mt-[4px]"), Some(("mt", px(-4))));
+        // Padding and sizes can't be negative
+        assert_eq!(spacing("-p-2"), None);
+        assert_eq!(spacing("-w-4"), None);
+        assert_eq!(spacing("-left-auto"), None);
+    }
+
+    #[test]
+    fn fractions_full_and_auto() {
+        assert_eq!(spacing("w-1/2"), Some(("w", percent(50))));
+        assert_eq!(spacing("basis-3/4"), Some(("basis", percent(75))));
+        assert_eq!(spacing("h-full"), Some(("h", percent(100))));
+        assert_eq!(spacing("w-auto"), Some(("w", auto())));
+        assert_eq!(spacing("w-1/0"), None);
+        assert_eq!(spacing("w-a/2"), None);
+        // Padding doesn't take fractions
+        assert_eq!(spacing("p-1/2"), None);
+        assert_eq!(spacing("p-full"), None);
+        assert_eq!(spacing("p-auto"), None);
+    }
+
+    #[test]
+    fn arbitrary_spacing() {
+        assert_eq!(spacing("p-[1.5rem]"), Some(("p", px(24))));
+        assert_eq!(spacing("w-[50vw]"), Some(("w", vw(50))));
+        assert_eq!(spacing("p-[1.5rem"), None);
+        assert_eq!(spacing("p-[wide]"), None);
+        assert_eq!(spacing("p-unknown"), None);
+    }
+
+    #[test]
+    fn theme_spacing() {
+        let mut ctx = context();
+        ctx.theme
+            .spacing
+            .insert("gutter".to_string(), "24px".to_string());
+        ctx.theme.spacing.insert("4".to_string(), "1px".to_string());
+        assert_eq!(spacing_class("p-gutter", &ctx), Some(("p", px(24))));
+        // Theme tokens override steps of the scale
+        assert_eq!(spacing_class("p-4", &ctx), Some(("p", px(1))));
+    }
+
+    #[test]
+    fn unknown_utilities() {
+        assert_eq!(spacing("foo-4"), None);
+        assert_eq!(spacing("p"), None);
+        assert_eq!(spacing("bg-4"), None);
+    }
+
+    #[test]
+    fn palette_colors() {
+        let theme = HtmlTheme::default();
+        let red: Color = tailwind_color("red", "500").unwrap().into();
+        assert_eq!(color_class("bg-red-500", &theme), Some(("bg", red)));
+        assert_eq!(
+            color_class("text-white", &theme),
+            Some(("text", Color::WHITE))
+        );
+        assert_eq!(
+            color_class("bg-transparent", &theme),
+            Some(("bg", Color::NONE))
+        );
+        assert_eq!(
+            color_class("border-x-red-500", &theme),
+            Some(("border-x", red))
+        );
+        // `border-t` is only a prefix of the color name
+        let teal: Color = tailwind_color("teal", "500").unwrap().into();
+        assert_eq!(
+            color_class("border-teal-500", &theme),
+            Some(("border", teal))
+        );
+        assert_eq!(color_class("bg-red-550", &theme), None);
+        assert_eq!(color_class("bg-mauve-500", &theme), None);
+        assert_eq!(color_class("bg-red", &theme), None);
+        assert_eq!(color_class("shadow-red-500", &theme), None);
+    }
+
+    #[test]
+    fn arbitrary_colors() {
+        let theme = HtmlTheme::default();
+        let orange = Color::srgb_u8(0xff, 0x88, 0x00);
+        assert_eq!(color_class("bg-[#ff8800]", &theme), Some(("bg", orange)));
+        assert_eq!(
+            color_class("bg-[rgb(255_136_0)]", &theme),
+            Some(("bg", orange))
+        );
+        assert_eq!(color_class("bg-[#ff8800", &theme), None);
+        assert_eq!(color_class("bg-[nonsense]", &theme), None);
+    }
+
+    #[test]
+    fn theme_colors() {
+        let mut theme = HtmlTheme::default();
+        theme
+            .colors
+            .insert("brand".to_string(), "#ff8800".to_string());
+        theme
+            .colors
+            .insert("broken".to_string(), "not a color".to_string());
+        let brand = Color::srgb_u8(0xff, 0x88, 0x00);
+        assert_eq!(color_class("bg-brand", &theme), Some(("bg", brand)));
+        assert_eq!(
+            color_class("bg-brand/50", &theme),
+            Some(("bg", brand.with_alpha(0.5)))
+        );
+        assert_eq!(color_class("bg-broken", &theme), None);
+    }
+
+    #[test]
+    fn color_opacity_modifiers() {
+        let theme = HtmlTheme::default();
+        assert_eq!(
+            color_class("bg-black/50", &theme),
+            Some(("bg", Color::BLACK.with_alpha(0.5)))
+        );
+        assert_eq!(
+            color_class("bg-white/0", &theme),
+            Some(("bg", Color::WHITE.with_alpha(0.0)))
+        );
+        assert_eq!(
+            color_class("bg-black/[0.35]", &theme),
+            Some(("bg", Color::BLACK.with_alpha(0.35)))
+        );
+        assert_eq!(
+            color_class("bg-black/[35%]", &theme),
+            Some(("bg", Color::BLACK.with_alpha(0.35)))
+        );
+        assert_eq!(
+            color_class("bg-[#ff8800]/25", &theme),
+            Some(("bg", Color::srgb_u8(0xff, 0x88, 0x00).with_alpha(0.25)))
+        );
+        // Arbitrary opacities are clamped, the scale is not
+        assert_eq!(
+            color_class("bg-black/[1.5]", &theme),
+            Some(("bg", Color::BLACK))
+        );
+        assert_eq!(color_class("bg-black/101", &theme), None);
+        assert_eq!(color_class("bg-black/-5", &theme), None);
+        assert_eq!(color_class("bg-black/half", &theme), None);
+        assert_eq!(color_class("bg-black/[inf]", &theme), None);
+        assert_eq!(color_class("bg-[#ff8800]25", &theme), None);
+    }
+
+    #[test]
+    fn arbitrary_lengths() {
+        assert_eq!(arbitrary_length("12px", 16.0), Some(Val::Px(12.0)));
+        assert_eq!(arbitrary_length("12", 16.0), Some(Val::Px(12.0)));
+        assert_eq!(arbitrary_length("1.5rem", 16.0), Some(Val::Px(24.0)));
+        assert_eq!(arbitrary_length("1.5rem", 10.0), Some(Val::Px(15.0)));
+        assert_eq!(arbitrary_length("50%", 16.0), Some(Val::Percent(50.0)));
+        assert_eq!(arbitrary_length("10vw", 16.0), Some(Val::Vw(10.0)));
+        assert_eq!(arbitrary_length("10vh", 16.0), Some(Val::Vh(10.0)));
+        assert_eq!(arbitrary_length("10vmin", 16.0), Some(Val::VMin(10.0)));
+        assert_eq!(arbitrary_length("10vmax", 16.0), Some(Val::VMax(10.0)));
+        assert_eq!(arbitrary_length("-4px", 16.0), Some(Val::Px(-4.0)));
+    }
+
+    #[test]
+    fn invalid_arbitrary_lengths() {
+        assert_eq!(arbitrary_length("", 16.0), None);
+        assert_eq!(arbitrary_length("px", 16.0), None);
+        assert_eq!(arbitrary_length("12em", 16.0), None);
+        assert_eq!(arbitrary_length("12 px", 16.0), None);
+        assert_eq!(arbitrary_length("wide", 16.0), None);
+        assert_eq!(arbitrary_length("infpx", 16.0), None);
+        assert_eq!(arbitrary_length("NaN%", 16.0), None);
+    }
+
+    fn parse(classes: &str, ctx: &StyleContext) -> Result<Style, StyleError> {
+        Style::parse(classes, "", ctx, &VariantState::default(), None)
+    }
+
+    #[test]
+    fn numeric_utilities() {
+        let style = parse(
+            "border-2 border-x-4 -z-10 col-span-3 row-span-2",
+            &context(),
+        )
+        .unwrap();
+        assert_eq!(style.border, UiRect::new(px(4), px(4), px(2), px(2)));
+        assert_eq!(style.z_index, ZIndex(-10));
+        assert_eq!(style.grid_column, GridPlacement::span(3));
+        assert_eq!(style.grid_row, GridPlacement::span(2));
+    }
+
+    #[test]
+    fn numeric_utilities_out_of_range() {
+        let lenient = StyleContext {
+            unsupported: UnsupportedClasses::Warn,
+            ..context()
+        };
+        for class in [
+            "border-t-99999999999999999999",
+            "z-99999999999",
+            "col-span-70000",
+            "row-span-0",
+        ] {
+            assert!(
+                matches!(
+                    parse(class, &context()),
+                    Err(StyleError::UnsupportedClass(unsupported)) if unsupported == class
+                ),
+                "{class}"
+            );
+            assert!(parse(class, &lenient).is_ok(), "{class}");
+        }
+    }
+
+    #[test]
+    fn element_and_position_variants() {
+        let theme = HtmlTheme::default();
+        let state = VariantState {
+            element: ElementState {
+                hovered: true,
+                checked: true,
+                ..default()
+            },
+            position: SiblingPosition { index: 2, count: 3 },
+            ..default()
+        };
+        assert_eq!(state.matches(&[], &theme), Some(true));
+        assert_eq!(state.matches(&["hover"], &theme), Some(true));
+        assert_eq!(state.matches(&["hover", "checked"], &theme), Some(true));
+        assert_eq!(state.matches(&["hover", "focus"], &theme), Some(false));
+        assert_eq!(state.matches(&["active"], &theme), Some(false));
+        assert_eq!(state.matches(&["disabled"], &theme), Some(false));
+        assert_eq!(state.matches(&["last", "odd"], &theme), Some(true));
+        assert_eq!(state.matches(&["first"], &theme), Some(false));
+        assert_eq!(state.matches(&["only"], &theme), Some(false));
+        assert_eq!(state.matches(&["even"], &theme), Some(false));
+        assert_eq!(state.matches(&["visited"], &theme), None);
+        assert_eq!(state.matches(&["hover", "visited"], &theme), None);
+    }
+
+    #[test]
+    fn group_and_peer_variants() {
+        let theme = HtmlTheme::default();
+        let state = VariantState {
+            group: ElementState {
+                hovered: true,
+                ..default()
+            },
+            peer: ElementState {
+                focused: true,
+                ..default()
+            },
+            ..default()
+        };
+        assert_eq!(state.matches(&["group-hover"], &theme), Some(true));
+        assert_eq!(state.matches(&["group-focus"], &theme), Some(false));
+        assert_eq!(state.matches(&["peer-focus"], &theme), Some(true));
+        assert_eq!(state.matches(&["peer-hover"], &theme), Some(false));
+        assert_eq!(state.matches(&["hover"], &theme), Some(false));
+        // Structural variants of the group aren't supported
+        assert_eq!(state.matches(&["group-first"], &theme), None);
+        assert_eq!(state.matches(&["peer-"], &theme), None);
+    }
+
+    #[test]
+    fn viewport_and_color_scheme_variants() {
+        let theme = HtmlTheme {
+            color_schemes: vec!["sepia".to_string()],
+            ..default()
+        };
+        let state = VariantState {
+            viewport: UVec2::new(800, 1000),
+            color_scheme: HtmlColorScheme::Dark,
+            ..default()
+        };
+        assert_eq!(state.matches(&["sm", "md"], &theme), Some(true));
+        assert_eq!(state.matches(&["lg"], &theme), Some(false));
+        assert_eq!(state.matches(&["portrait"], &theme), Some(true));
+        assert_eq!(state.matches(&["landscape"], &theme), Some(false));
+        assert_eq!(state.matches(&["dark", "md"], &theme), Some(true));
+        assert_eq!(state.matches(&["sepia"], &theme), Some(false));
+        assert_eq!(state.matches(&["light"], &theme), None);
+
+        let sepia = VariantState {
+            color_scheme: HtmlColorScheme::custom("sepia"),
+            ..state
+        };
+        assert_eq!(sepia.matches(&["sepia"], &theme), Some(true));
+        assert_eq!(sepia.matches(&["dark"], &theme), Some(false));
+    }
+
+    #[test]
+    fn variants_are_ordered_like_tailwind() {
+        let theme = HtmlTheme::default();
+        let order = |variants: &[&str]| variant_order(variants, &theme);
+
+        assert_eq!(order(&[]), 0);
+        assert!(order(&["group-hover"]) < order(&["first"]));
+        assert!(order(&["peer-focus"]) < order(&["hover"]));
+        assert!(order(&["checked"]) < order(&["hover"]));
+        assert!(order(&["hover"]) < order(&["focus"]));
+        assert!(order(&["focus"]) < order(&["active"]));
+        assert!(order(&["active"]) < order(&["disabled"]));
+        assert!(order(&["disabled"]) < order(&["sm"]));
+        assert!(order(&["sm"]) < order(&["md"]));
+        assert!(order(&["xl"]) < order(&["2xl"]));
+        assert!(order(&["2xl"]) < order(&["dark"]));
+        // The last variant of a class decides
+        assert_eq!(order(&["md", "hover"]), order(&["md"]));
+        assert_eq!(order(&["hover", "md"]), order(&["md"]));
+    }
+
+    #[test]
+    fn later_variants_take_precedence() {
+        let state = VariantState {
+            element: ElementState {
+                hovered: true,
+                disabled: true,
+                ..default()
+            },
+            viewport: UVec2::new(1100, 600),
+            ..default()
+        };
+        let ctx = context();
+        let style = Style::parse(
+            "md:w-8 lg:w-4 sm:w-2 disabled:bg-red-500 hover:bg-blue-500 group-hover:bg-white",
+            "",
+            &ctx,
+            &state,
+            None,
+        )
+        .unwrap();
+        assert_eq!(style.width, px(16));
+        assert_eq!(
+            style.background_color.0,
+            Color::from(tailwind_color("red", "500").unwrap())
+        );
+    }
+
+    #[test]
+    fn color_scheme_only_differs_for_scheme_variants() {
+        let mut ctx = context();
+        ctx.stylesheet
+            .parse(".panel { @apply dark:bg-black }", UnsupportedClasses::Error)
+            .unwrap();
+        let light = VariantState::default();
+        let dark = VariantState {
+            color_scheme: HtmlColorScheme::Dark,
+            ..default()
+        };
+
+        assert!(light.differs(&dark, "p-2 dark:bg-black", &ctx));
+        assert!(light.differs(&dark, "panel", &ctx));
+        assert!(!light.differs(&dark, "p-2 hover:bg-white", &ctx));
+        assert!(!light.differs(&dark, "", &ctx));
+        assert!(!light.differs(&light, "dark:bg-black", &ctx));
+
+        // Other changes don't depend on the classes
+        let hovered = VariantState {
+            element: ElementState {
+                hovered: true,
+                ..default()
+            },
+            ..default()
+        };
+        assert!(light.differs(&hovered, "", &ctx));
+    }
+}
@@ -0,0 +1,41 @@
+//! Helpers shared by the tests of the parsers
+
+use bevy::platform::collections::HashMap;
+
+use crate::internal::dom::FontFamily;
+use crate::internal::markup::RawNode;
+use crate::internal::stylesheet::Stylesheet;
+use crate::internal::tailwind::StyleContext;
+use crate::internal::tailwind::UnsupportedClasses;
+use crate::theme::HtmlTheme;
+
+/// Context with the default theme, a root font size of 16px and errors for unsupported classes
+pub(crate) fn context() -> StyleContext {
+    StyleContext {
+        fonts: HashMap::new(),
+        default_font: FontFamily::default(),
+        unsupported: UnsupportedClasses::Error,
+        rem: 16.0,
+        theme: HtmlTheme::default(),
+        stylesheet: Stylesheet::default(),
+    }
+}
+
+/// Compact form of a tree with every element closed explicitly
+pub(crate) fn render(nodes: &[RawNode]) -> String {
+    nodes
+        .iter()
+        .map(|node| match node {
+            RawNode::Text(text) => text.clone(),
+            RawNode::Element(element) => {
+                let attributes = element
+                    .attributes
+                    .iter()
+                    .map(|(name, value)| format!(" {name}=\"{value}\""))
+                    .collect::<String>();
+                let children = render(&element.children);
+                format!("<{0}{attributes}>{children}</{0}>", element.name)
+            }
+        })
+        .collect()
+}
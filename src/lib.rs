@@ -1,41 +1,99 @@
+use std::sync::Arc;
+
 use bevy::prelude::*;
 
 use crate::assets::HtmlTailwind;
 use crate::assets::HtmlUiAssetLoader;
 use crate::registry::HtmlTailwindRegistry;
+use crate::style::apply_class_changes;
+use crate::style::restyle_nodes;
+use crate::style::separate_plain_children;
+use crate::style::update_variant_state;
+use crate::systems::apply_attributes;
 use crate::systems::apply_markers;
+use crate::systems::build_custom_elements;
 use crate::systems::spawn_ui;
 use crate::systems::sync_system;
+use crate::theme::HtmlColorScheme;
+use crate::theme::HtmlTheme;
 
 pub mod prelude {
     pub use crate::HtmlTailwindPlugin;
     pub use crate::assets::HtmlTailwind;
+    pub use crate::assets::HtmlUiLoaderSettings;
+    pub use crate::assets::ParseMode;
+    pub use crate::bundle::HtmlAttributes;
+    pub use crate::bundle::HtmlClasses;
+    pub use crate::bundle::HtmlClassesExt;
     pub use crate::bundle::HtmlTailwindBundle;
+    pub use crate::registry::HtmlElement;
     pub use crate::registry::HtmlTailwindAppExt;
+    pub use crate::theme::HtmlColorScheme;
+    pub use crate::theme::HtmlTheme;
+    pub use crate::theme::ThemeFontSize;
 }
 
 mod assets;
 mod bundle;
 mod internal;
 mod registry;
+mod style;
 mod systems;
+mod theme;
 
 pub struct HtmlTailwindPlugin {
     pub hot_reload: bool,
+    /// Design tokens that are available in every document
+    pub theme: HtmlTheme,
+    /// Path of a RON file in the assets directory that extends the theme, e.g. `theme.ron`
+    ///
+    /// Changes of the file reload all documents.
+    pub theme_file: Option<String>,
 }
 
 impl Default for HtmlTailwindPlugin {
     fn default() -> Self {
-        Self { hot_reload: true }
+        Self {
+            hot_reload: true,
+            theme: HtmlTheme::default(),
+            theme_file: None,
+        }
     }
 }
 
 impl Plugin for HtmlTailwindPlugin {
     fn build(&self, app: &mut App) {
+        let registry = HtmlTailwindRegistry::default();
+
         app.init_asset::<HtmlTailwind>()
-            .init_asset_loader::<HtmlUiAssetLoader>()
-            .init_resource::<HtmlTailwindRegistry>()
-            .add_systems(Update, (spawn_ui, apply_markers).chain());
+            .register_asset_loader(HtmlUiAssetLoader {
+                theme: Arc::new(self.theme.clone()),
+                theme_file: self.theme_file.clone(),
+                custom_elements: registry.element_tags(),
+            })
+            .insert_resource(registry)
+            .init_resource::<HtmlColorScheme>()
+            .add_systems(
+                Update,
+                (
+                    spawn_ui,
+                    apply_markers,
+                    apply_attributes,
+                    build_custom_elements,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    update_variant_state,
+                    apply_class_changes,
+                    restyle_nodes,
+                    separate_plain_children,
+                )
+                    .chain()
+                    .after(apply_markers),
+            );
 
         if self.hot_reload {
             app.add_systems(Update, sync_system);
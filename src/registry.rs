@@ -1,12 +1,101 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
 
 use bevy::prelude::*;
+use bevy::text::LineHeight;
+
+use crate::internal::tailwind::Style;
+
+/// Function that applies a marker component to an entity
+type MarkerFn = Box<dyn Fn(&mut EntityCommands) + Send + Sync>;
+
+/// Function that handles the value of an attribute
+type AttributeFn = Box<dyn Fn(&str, &mut EntityCommands) + Send + Sync>;
+
+/// Function that builds the node of a custom element
+type ElementFn = Box<dyn Fn(&HtmlElement, &mut EntityCommands) + Send + Sync>;
 
 /// Resource for managing HTML marker components and custom fonts
 #[derive(Resource, Default)]
 pub struct HtmlTailwindRegistry {
     /// Lookup for HTML ids and the corresponding function to apply the marker component
-    markers: HashMap<String, Vec<Box<dyn Fn(&mut EntityCommands) + Send + Sync>>>,
+    markers: HashMap<String, Vec<MarkerFn>>,
+    /// Lookup for attribute names and the functions handling their values
+    attributes: HashMap<String, Vec<AttributeFn>>,
+    /// Lookup for tags of custom elements and the function building their node
+    elements: HashMap<String, ElementFn>,
+    /// Tags of the custom elements, shared with the asset loader
+    element_tags: Arc<RwLock<HashSet<String>>>,
+}
+
+/// Custom element of a document, passed to the function registered for its tag
+///
+/// The resolved style is already inserted when the function is called. Components of the
+/// style, like `Node`, `BackgroundColor` or `TextFont`, are overwritten when the classes or the
+/// state of the element change at runtime and their value in the style changes.
+pub struct HtmlElement<'a> {
+    /// Lowercase tag name
+    pub tag: &'a str,
+    /// Attributes in the order of the document
+    pub attributes: &'a [(String, String)],
+    /// Style resolved from the classes of the element
+    pub(crate) style: &'a Style,
+}
+
+impl HtmlElement<'_> {
+    /// Value of the attribute with the given name
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Layout resolved from the classes and the `style` attribute of the element
+    pub fn node(&self) -> Node {
+        self.style.to_node().node
+    }
+
+    /// Resolved background color, including the opacity of the element
+    pub fn background_color(&self) -> Color {
+        self.style.fade(self.style.background_color.0)
+    }
+
+    /// Resolved text color, including the opacity of the element
+    pub fn text_color(&self) -> Color {
+        self.style.fade(self.style.text_color.0)
+    }
+
+    /// Resolved border colors, including the opacity of the element
+    pub fn border_color(&self) -> BorderColor {
+        self.style.to_node().border_color
+    }
+
+    /// Resolved font, size, weight and smoothing
+    ///
+    /// The font is the face of the `font-*` family matching the weight and `italic`, or the
+    /// default font.
+    pub fn text_font(&self) -> TextFont {
+        self.style.text_font.clone()
+    }
+
+    /// Resolved line height, e.g. the one of `text-xl`
+    pub fn line_height(&self) -> LineHeight {
+        self.style.line_height
+    }
+
+    /// Resolved text alignment and line breaking
+    pub fn text_layout(&self) -> TextLayout {
+        self.style.text_layout
+    }
+
+    /// Resolved outline, including the opacity of the element
+    pub fn outline(&self) -> Outline {
+        self.style.to_node().outline
+    }
 }
 
 impl HtmlTailwindRegistry {
@@ -32,6 +121,51 @@ impl HtmlTailwindRegistry {
             }));
     }
 
+    pub(crate) fn add_attribute(
+        &mut self,
+        name: String,
+        handle: impl Fn(&str, &mut EntityCommands) + Send + Sync + 'static,
+    ) {
+        self.attributes
+            .entry(name)
+            .or_default()
+            .push(Box::new(handle));
+    }
+
+    pub(crate) fn add_element(
+        &mut self,
+        tag: String,
+        build: impl Fn(&HtmlElement, &mut EntityCommands) + Send + Sync + 'static,
+    ) {
+        let tag = tag.to_lowercase();
+        self.element_tags
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(tag.clone());
+        self.elements.insert(tag, Box::new(build));
+    }
+
+    /// Tags of the custom elements, which are updated when more elements are registered
+    pub(crate) fn element_tags(&self) -> Arc<RwLock<HashSet<String>>> {
+        self.element_tags.clone()
+    }
+
+    /// Builds the node of a custom element with the registered function
+    pub(crate) fn build_element(&self, element: &HtmlElement, commands: &mut EntityCommands) {
+        if let Some(build) = self.elements.get(element.tag) {
+            build(element, commands);
+        }
+    }
+
+    /// Applies the handlers registered for an attribute to an entity
+    pub(crate) fn apply_attribute(&self, name: &str, value: &str, commands: &mut EntityCommands) {
+        if let Some(handlers) = self.attributes.get(name) {
+            for handle in handlers {
+                handle(value, commands);
+            }
+        }
+    }
+
     /// Applies registered markers to an entity
     pub(crate) fn apply_markers(&self, html_id: &str, commands: &mut EntityCommands) {
         if let Some(markers) = self.markers.get(html_id) {
@@ -42,7 +176,7 @@ impl HtmlTailwindRegistry {
     }
 }
 
-/// Extension trait for registering HTML markers
+/// Extension trait for registering HTML markers, attributes and custom elements
 pub trait HtmlTailwindAppExt {
     fn register_html_marker<M: Component + Default>(
         &mut self,
@@ -54,6 +188,25 @@ pub trait HtmlTailwindAppExt {
         html_id: impl Into<String>,
         marker: M,
     ) -> &mut Self;
+
+    /// Register a handler for an attribute, which is called with its value when a node with
+    /// the attribute is spawned, e.g. `data-tooltip="Heals 10 HP"`
+    fn register_html_attribute(
+        &mut self,
+        name: impl Into<String>,
+        handle: impl Fn(&str, &mut EntityCommands) + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    /// Register a custom element, which is spawned like a `<div>` and built further by the
+    /// function, e.g. `<health-bar max="100"/>`
+    ///
+    /// Components inserted by the function that are also part of the style are overwritten
+    /// when the element is restyled, see [`HtmlElement`].
+    fn register_html_element(
+        &mut self,
+        tag: impl Into<String>,
+        build: impl Fn(&HtmlElement, &mut EntityCommands) + Send + Sync + 'static,
+    ) -> &mut Self;
 }
 
 impl HtmlTailwindAppExt for App {
@@ -83,4 +236,79 @@ impl HtmlTailwindAppExt for App {
 
         self
     }
+
+    fn register_html_attribute(
+        &mut self,
+        name: impl Into<String>,
+        handle: impl Fn(&str, &mut EntityCommands) + Send + Sync + 'static,
+    ) -> &mut Self {
+        let name = name.into();
+
+        self.world_mut()
+            .resource_mut::<HtmlTailwindRegistry>()
+            .add_attribute(name, handle);
+
+        self
+    }
+
+    fn register_html_element(
+        &mut self,
+        tag: impl Into<String>,
+        build: impl Fn(&HtmlElement, &mut EntityCommands) + Send + Sync + 'static,
+    ) -> &mut Self {
+        let tag = tag.into();
+
+        self.world_mut()
+            .resource_mut::<HtmlTailwindRegistry>()
+            .add_element(tag, build);
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::text::FontWeight;
+    use bevy::text::Justify;
+
+    use super::*;
+    use crate::internal::tailwind::VariantState;
+    use crate::internal::testing::context;
+
+    fn style(classes: &str) -> Style {
+        Style::parse(classes, "", &context(), &VariantState::default(), None).unwrap()
+    }
+
+    #[test]
+    fn resolved_style() {
+        let style = style(
+            "w-8 bg-white text-xl font-bold text-center text-red-500 outline-2 outline-offset-1 \
+             opacity-50",
+        );
+        let element = HtmlElement {
+            tag: "health-bar",
+            attributes: &[("max".to_string(), "250".to_string())],
+            style: &style,
+        };
+
+        assert_eq!(element.attribute("max"), Some("250"));
+        assert_eq!(element.attribute("min"), None);
+        assert_eq!(element.node().width, px(32));
+        assert_eq!(element.background_color(), Color::WHITE.with_alpha(0.5));
+
+        let text_font = element.text_font();
+        assert_eq!(text_font.font_size, 20.0);
+        assert_eq!(text_font.weight, FontWeight::BOLD);
+        assert_eq!(
+            element.line_height(),
+            LineHeight::RelativeToFont(1.75 / 1.25)
+        );
+        assert_eq!(element.text_layout().justify, Justify::Center);
+
+        let outline = element.outline();
+        assert_eq!(outline.width, px(2));
+        assert_eq!(outline.offset, px(1));
+        assert_eq!(outline.color, element.text_color());
+        assert_eq!(outline.color.alpha(), 0.5);
+    }
 }
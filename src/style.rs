@@ -0,0 +1,879 @@
+//! Resolving the style of spawned nodes again when their state or classes change
+
+use std::sync::Arc;
+
+use bevy::ecs::entity::EntityHashSet;
+use bevy::ecs::system::SystemParam;
+use bevy::input_focus::InputFocus;
+use bevy::prelude::*;
+use bevy::text::Strikethrough;
+use bevy::text::Underline;
+use bevy::ui::Checked;
+use bevy::ui::ComputedUiRenderTargetInfo;
+use bevy::ui::InteractionDisabled;
+use bevy::window::PrimaryWindow;
+use log::warn;
+
+use crate::bundle::HtmlClasses;
+use crate::internal::stylesheet::Stylesheet;
+use crate::internal::tailwind::ElementState;
+use crate::internal::tailwind::SiblingPosition;
+use crate::internal::tailwind::Style;
+use crate::internal::tailwind::StyleContext;
+use crate::internal::tailwind::VariantState;
+use crate::internal::tailwind::split_variants;
+use crate::theme::HtmlColorScheme;
+
+/// Marks a node with the `group` class, whose descendants use its state for `group-*` variants
+#[derive(Component, Debug)]
+pub(crate) struct HtmlGroup;
+
+/// Marks a node with the `peer` class, whose following siblings use its state for `peer-*`
+/// variants
+#[derive(Component, Debug)]
+pub(crate) struct HtmlPeer;
+
+/// Resolved style of a spawned node
+#[derive(Component, Debug, Clone)]
+pub(crate) struct HtmlNodeStyle {
+    pub context: Arc<StyleContext>,
+    /// State the style was resolved with
+    pub state: VariantState,
+    pub style: Style,
+    /// Declarations of the `style` attribute, applied after the classes
+    pub inline_style: String,
+    /// The node is a `TextSpan` instead of a UI node
+    pub span: bool,
+    /// The style has to be resolved again
+    pub dirty: bool,
+}
+
+/// Everything besides the nodes that is required to spawn a document
+pub(crate) struct SpawnContext {
+    pub context: Arc<StyleContext>,
+    /// Viewport and color scheme the nodes start with
+    pub state: VariantState,
+}
+
+/// State the nodes of a document start with
+///
+/// The viewport is the one of the render target of the entity the document is spawned on, or
+/// the primary window if it isn't known yet.
+pub(crate) fn spawn_state(
+    target: Option<&ComputedUiRenderTargetInfo>,
+    window: Vec2,
+    color_scheme: &HtmlColorScheme,
+) -> VariantState {
+    VariantState {
+        viewport: viewport(target, window),
+        color_scheme: color_scheme.clone(),
+        ..Default::default()
+    }
+}
+
+impl HtmlNodeStyle {
+    pub(crate) fn new(style: &Style, spawn: &SpawnContext) -> Self {
+        Self {
+            context: spawn.context.clone(),
+            state: spawn.state.clone(),
+            style: style.clone(),
+            inline_style: String::new(),
+            span: false,
+            dirty: false,
+        }
+    }
+
+    /// Mark the node as a `TextSpan`
+    pub(crate) fn span(mut self) -> Self {
+        self.span = true;
+        self
+    }
+
+    /// Declarations of the `style` attribute
+    pub(crate) fn with_inline_style(mut self, inline_style: &str) -> Self {
+        self.inline_style = inline_style.to_string();
+        self
+    }
+
+    /// State of the node itself when it is spawned
+    pub(crate) fn with_element_state(mut self, element: ElementState) -> Self {
+        self.state.element = element;
+        self
+    }
+
+    /// Insert the component, the classes and the components of the style
+    ///
+    /// The style was resolved while loading, without a viewport and with the default color
+    /// scheme, so it is resolved again if variants of them apply.
+    pub(crate) fn spawn(mut self, commands: &mut EntityCommands, classes: &str) {
+        let loaded = VariantState {
+            viewport: UVec2::ZERO,
+            color_scheme: HtmlColorScheme::default(),
+            ..self.state.clone()
+        };
+        self.dirty = loaded.differs(&self.state, classes, &self.context);
+        insert_markers(commands, classes, &self.context.stylesheet, self.span);
+        patch_style(commands, None, &self.style, self.span);
+        commands.insert((self, HtmlClasses::new(classes)));
+    }
+}
+
+/// Insert the components that the classes of a node require
+///
+/// Nodes using interaction variants like `hover:` get an [`Interaction`], as well as
+/// groups and peers. Component classes of stylesheets count with their utilities.
+fn insert_markers(
+    commands: &mut EntityCommands,
+    classes: &str,
+    stylesheet: &Stylesheet,
+    span: bool,
+) {
+    let mut interactive = false;
+    let mut group = false;
+    let mut peer = false;
+    for class in stylesheet.expand(classes) {
+        match class.as_ref() {
+            "group" => group = true,
+            "peer" => peer = true,
+            class => {
+                interactive |= split_variants(class)
+                    .0
+                    .iter()
+                    .any(|variant| matches!(*variant, "hover" | "active"));
+            }
+        }
+    }
+
+    if group {
+        commands.insert(HtmlGroup);
+    } else {
+        commands.remove::<HtmlGroup>();
+    }
+    if peer {
+        commands.insert(HtmlPeer);
+    } else {
+        commands.remove::<HtmlPeer>();
+    }
+    if (interactive || group || peer) && !span {
+        commands.entry::<Interaction>().or_default();
+    }
+}
+
+/// Insert the components of a style that differ from the previous style
+///
+/// Without a previous style all components are inserted.
+pub(crate) fn patch_style(
+    commands: &mut EntityCommands,
+    old: Option<&Style>,
+    new: &Style,
+    span: bool,
+) {
+    if span {
+        if old.is_none_or(|old| old.text_font != new.text_font) {
+            commands.insert(new.text_font.clone());
+        }
+        let text_color = new.fade(new.text_color.0);
+        if old.is_none_or(|old| old.fade(old.text_color.0) != text_color) {
+            commands.insert(TextColor(text_color));
+        }
+    } else if let Some(old) = old {
+        let old = old.to_node();
+        let new = new.to_node();
+
+        macro_rules! patch {
+            ($($field:ident),*) => {
+                $(
+                    if old.$field != new.$field {
+                        commands.insert(new.$field);
+                    }
+                )*
+            };
+        }
+        patch!(
+            node,
+            visibility,
+            border_color,
+            background_color,
+            text_color,
+            z_index,
+            text_font,
+            line_height,
+            outline,
+            transform
+        );
+        if old.text_layout.justify != new.text_layout.justify
+            || old.text_layout.linebreak != new.text_layout.linebreak
+        {
+            commands.insert(new.text_layout);
+        }
+    } else {
+        commands.insert(new.to_node());
+    }
+
+    if old.is_none_or(|old| old.underline != new.underline) {
+        if new.underline {
+            commands.insert(Underline);
+        } else {
+            commands.remove::<Underline>();
+        }
+    }
+    if old.is_none_or(|old| old.strikethrough != new.strikethrough) {
+        if new.strikethrough {
+            commands.insert(Strikethrough);
+        } else {
+            commands.remove::<Strikethrough>();
+        }
+    }
+}
+
+/// Interaction state of nodes
+type ElementQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Option<&'static Interaction>,
+        Has<InteractionDisabled>,
+        Has<Checked>,
+    ),
+>;
+
+/// Nodes with their position in the hierarchy
+type StyleTreeQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut HtmlNodeStyle,
+        &'static HtmlClasses,
+        Option<&'static ChildOf>,
+        Option<&'static Children>,
+    ),
+>;
+
+/// Update the state of nodes from their [`Interaction`], the [`InputFocus`],
+/// [`InteractionDisabled`], [`Checked`], their position among their siblings, the size of
+/// their viewport and the [`HtmlColorScheme`]
+///
+/// The viewport is the one of the target camera, or the primary window if it isn't known yet.
+/// Only nodes whose own state or the state of their group or peer may have changed are visited,
+/// besides all nodes when the window or the color scheme changes.
+pub(crate) fn update_variant_state(
+    mut changes: StateChanges,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut nodes: Query<(
+        &mut HtmlNodeStyle,
+        &HtmlClasses,
+        Option<&ComputedUiRenderTargetInfo>,
+    )>,
+    elements: ElementQuery,
+    relatives: Relatives,
+) {
+    let focused = changes.focus.as_ref().and_then(|focus| focus.0);
+    let window = window.single().map(Window::size).unwrap_or_default();
+    let color_scheme = changes.color_scheme.clone();
+    let element_state = |entity| element_state(entity, focused, &elements);
+
+    // The viewport and the color scheme don't depend on the relatives of a node
+    if changes.global() {
+        for (node, classes, target) in &mut nodes {
+            let state = VariantState {
+                viewport: viewport(target, window),
+                color_scheme: color_scheme.clone(),
+                ..node.state.clone()
+            };
+            set_state(node, classes, state);
+        }
+    }
+
+    for entity in changes.seeds(&relatives) {
+        let Ok((node, classes, target)) = nodes.get_mut(entity) else {
+            continue;
+        };
+
+        // Finding the position is linear in the number of siblings, so it is only done when
+        // they change
+        let position = relatives
+            .position(entity, node.is_added())
+            .unwrap_or(node.state.position);
+
+        let state = VariantState {
+            element: element_state(entity),
+            position,
+            group: relatives
+                .group(entity)
+                .map(element_state)
+                .unwrap_or_default(),
+            peer: relatives
+                .peer(entity)
+                .map(element_state)
+                .unwrap_or_default(),
+            viewport: viewport(target, window),
+            color_scheme: color_scheme.clone(),
+        };
+        set_state(node, classes, state);
+    }
+}
+
+/// Store the state of a node, marking it dirty if other variants of its classes apply
+fn set_state(mut node: Mut<HtmlNodeStyle>, classes: &HtmlClasses, state: VariantState) {
+    if node.state != state {
+        if node.state.differs(&state, classes.as_str(), &node.context) {
+            node.dirty = true;
+        }
+        node.state = state;
+    }
+}
+
+/// A component that the state of nodes depends on was added
+type MarkerAdded = Or<(
+    Added<InteractionDisabled>,
+    Added<Checked>,
+    Added<HtmlGroup>,
+    Added<HtmlPeer>,
+)>;
+
+/// Changes that the state of nodes depends on
+#[derive(SystemParam)]
+pub(crate) struct StateChanges<'w, 's> {
+    focus: Option<Res<'w, InputFocus>>,
+    /// Entity that had the focus in the last update
+    last_focus: Local<'s, Option<Entity>>,
+    color_scheme: Res<'w, HtmlColorScheme>,
+    windows: Query<'w, 's, (), Changed<Window>>,
+    targets: Query<'w, 's, Entity, (Changed<ComputedUiRenderTargetInfo>, With<HtmlClasses>)>,
+    /// `HtmlClasses` is added together with `HtmlNodeStyle`, which is borrowed mutably
+    nodes: Query<'w, 's, Entity, Added<HtmlClasses>>,
+    interactions: Query<'w, 's, Entity, Changed<Interaction>>,
+    children: Query<'w, 's, &'static Children, Changed<Children>>,
+    parents: Query<'w, 's, Entity, Changed<ChildOf>>,
+    markers: Query<'w, 's, Entity, MarkerAdded>,
+    removed_disabled: RemovedComponents<'w, 's, InteractionDisabled>,
+    removed_checked: RemovedComponents<'w, 's, Checked>,
+    removed_groups: RemovedComponents<'w, 's, HtmlGroup>,
+    removed_peers: RemovedComponents<'w, 's, HtmlPeer>,
+}
+
+impl StateChanges<'_, '_> {
+    /// Whether the viewport or the color scheme of all nodes may have changed
+    fn global(&self) -> bool {
+        self.color_scheme.is_changed() || !self.windows.is_empty()
+    }
+
+    /// Nodes whose state may have changed since the last update
+    ///
+    /// Removals are read in any case, so they aren't reported again.
+    fn seeds(&mut self, relatives: &Relatives) -> EntityHashSet {
+        let mut seeds = EntityHashSet::default();
+
+        // The element state of these entities changed, which their group members and peers
+        // depend on as well
+        let mut changed = self.interactions.iter().collect::<Vec<_>>();
+        if let Some(focus) = &self.focus
+            && focus.is_changed()
+        {
+            changed.extend(self.last_focus.take());
+            changed.extend(focus.0);
+            *self.last_focus = focus.0;
+        }
+        for entity in changed {
+            relatives.add_dependents(entity, false, &mut seeds);
+        }
+
+        // Markers that make an entity a group or peer may have been removed as well
+        let markers = self
+            .markers
+            .iter()
+            .chain(self.removed_disabled.read())
+            .chain(self.removed_checked.read())
+            .chain(self.removed_groups.read())
+            .chain(self.removed_peers.read())
+            .collect::<Vec<_>>();
+        for entity in markers {
+            relatives.add_dependents(entity, true, &mut seeds);
+        }
+
+        // The position and the peer depend on the siblings, the group on the ancestors
+        for children in &self.children {
+            seeds.extend(children.iter());
+        }
+        for entity in &self.parents {
+            seeds.insert(entity);
+            seeds.extend(relatives.descendants.iter_descendants(entity));
+        }
+
+        seeds.extend(&self.nodes);
+        seeds.extend(&self.targets);
+        seeds
+    }
+}
+
+/// Logical size of the render target, or of the primary window if it isn't known yet
+fn viewport(target: Option<&ComputedUiRenderTargetInfo>, window: Vec2) -> UVec2 {
+    target
+        .map(ComputedUiRenderTargetInfo::logical_size)
+        .filter(|size| *size != Vec2::ZERO)
+        .unwrap_or(window)
+        .round()
+        .as_uvec2()
+}
+
+/// Finds the group and peer of nodes
+#[derive(SystemParam)]
+pub(crate) struct Relatives<'w, 's> {
+    groups: Query<'w, 's, (), With<HtmlGroup>>,
+    peers: Query<'w, 's, (), With<HtmlPeer>>,
+    parents: Query<'w, 's, &'static ChildOf>,
+    children: Query<'w, 's, Ref<'static, Children>>,
+    descendants: Query<'w, 's, &'static Children>,
+}
+
+impl Relatives<'_, '_> {
+    /// Closest ancestor with the `group` class
+    fn group(&self, entity: Entity) -> Option<Entity> {
+        self.parents
+            .iter_ancestors(entity)
+            .find(|ancestor| self.groups.contains(*ancestor))
+    }
+
+    /// Closest previous sibling with the `peer` class
+    fn peer(&self, entity: Entity) -> Option<Entity> {
+        if self.peers.is_empty() {
+            return None;
+        }
+
+        let parent = self.parents.get(entity).ok()?.parent();
+        self.children
+            .get(parent)
+            .ok()?
+            .iter()
+            .take_while(|sibling| *sibling != entity)
+            .filter(|sibling| self.peers.contains(*sibling))
+            .last()
+    }
+
+    /// Add the entity and all nodes whose group or peer it is to the seeds
+    ///
+    /// With `force`, the nodes are added even if the entity isn't a group or peer (anymore).
+    fn add_dependents(&self, entity: Entity, force: bool, seeds: &mut EntityHashSet) {
+        seeds.insert(entity);
+        if force || self.groups.contains(entity) {
+            seeds.extend(self.descendants.iter_descendants(entity));
+        }
+        if (force || self.peers.contains(entity))
+            && let Ok(parent) = self.parents.get(entity)
+            && let Ok(siblings) = self.descendants.get(parent.parent())
+        {
+            let following = siblings.iter().skip_while(|sibling| *sibling != entity);
+            seeds.extend(following.skip(1));
+        }
+    }
+
+    /// Position among the siblings, `None` if the children of the parent didn't change since
+    /// the last update
+    fn position(&self, entity: Entity, force: bool) -> Option<SiblingPosition> {
+        let Ok(parent) = self.parents.get(entity) else {
+            return Some(SiblingPosition { index: 0, count: 1 });
+        };
+        let siblings = self.children.get(parent.parent()).ok()?;
+        if !force && !siblings.is_changed() {
+            return None;
+        }
+
+        Some(SiblingPosition {
+            index: siblings.iter().position(|sibling| sibling == entity)?,
+            count: siblings.len(),
+        })
+    }
+}
+
+fn element_state(entity: Entity, focused: Option<Entity>, elements: &ElementQuery) -> ElementState {
+    let Ok((interaction, disabled, checked)) = elements.get(entity) else {
+        return ElementState::default();
+    };
+
+    ElementState {
+        hovered: matches!(
+            interaction,
+            Some(Interaction::Hovered | Interaction::Pressed)
+        ),
+        pressed: interaction == Some(&Interaction::Pressed),
+        focused: focused == Some(entity),
+        disabled,
+        checked,
+    }
+}
+
+/// Mark nodes whose [`HtmlClasses`] changed to be resolved again
+pub(crate) fn apply_class_changes(
+    mut commands: Commands,
+    mut nodes: Query<(Entity, Ref<HtmlClasses>, &mut HtmlNodeStyle), Changed<HtmlClasses>>,
+) {
+    for (entity, classes, mut node) in &mut nodes {
+        // The style of new nodes is already resolved
+        if classes.is_added() {
+            continue;
+        }
+
+        insert_markers(
+            &mut commands.entity(entity),
+            classes.as_str(),
+            &node.context.stylesheet,
+            node.span,
+        );
+        node.dirty = true;
+    }
+}
+
+/// Resolve the style of dirty nodes and their descendants again
+///
+/// Descendants are included because they inherit text properties.
+pub(crate) fn restyle_nodes(
+    mut commands: Commands,
+    mut nodes: StyleTreeQuery,
+    mut plain: PlainChildQuery,
+) {
+    let dirty = nodes
+        .iter()
+        .filter(|(_, node, ..)| node.dirty)
+        .map(|(entity, _, _, parent, _)| (entity, parent.map(ChildOf::parent)))
+        .collect::<Vec<_>>();
+
+    for (entity, parent) in dirty {
+        // The node was already resolved as a descendant of another dirty node
+        if nodes.get(entity).is_ok_and(|(_, node, ..)| !node.dirty) {
+            continue;
+        }
+
+        let parent_style = parent
+            .and_then(|parent| nodes.get(parent).ok())
+            .map(|(_, node, ..)| node.style.clone());
+        restyle(
+            &mut commands,
+            &mut nodes,
+            &mut plain,
+            entity,
+            parent_style.as_ref(),
+        );
+    }
+}
+
+fn restyle(
+    commands: &mut Commands,
+    nodes: &mut StyleTreeQuery,
+    plain: &mut PlainChildQuery,
+    entity: Entity,
+    parent: Option<&Style>,
+) {
+    let Ok((_, mut node, classes, _, children)) = nodes.get_mut(entity) else {
+        return;
+    };
+
+    match Style::parse(
+        classes.as_str(),
+        &node.inline_style,
+        &node.context,
+        &node.state,
+        parent,
+    ) {
+        Ok(style) => {
+            patch_style(
+                &mut commands.entity(entity),
+                Some(&node.style),
+                &style,
+                node.span,
+            );
+            if let Some(children) = children
+                && !node.style.same_separation(&style)
+            {
+                separate_children(commands, plain, &node.style, &style, children);
+            }
+            node.style = style;
+        }
+        Err(err) => warn!("Could not resolve the style of {entity}: {err}"),
+    }
+    node.dirty = false;
+
+    let style = node.style.clone();
+    let children = children
+        .map(|children| children.to_vec())
+        .unwrap_or_default();
+    for child in children {
+        restyle(commands, nodes, plain, child, Some(&style));
+    }
+}
+
+/// Marks a child that wasn't spawned from a document and has the margin and border of the
+/// `space-*` and `divide-*` of its parent
+#[derive(Component, Debug)]
+pub(crate) struct HtmlSeparated;
+
+/// Children that weren't spawned from a document
+type PlainChildQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Node,
+        &'static mut BorderColor,
+        Has<HtmlSeparated>,
+    ),
+    Without<HtmlNodeStyle>,
+>;
+
+/// Apply `space-*` and `divide-*` to children that weren't spawned from a document, e.g. rows
+/// added by a system
+///
+/// Children spawned from a document are handled when their style is resolved, and plain
+/// children when the separation of the parent changes.
+pub(crate) fn separate_plain_children(
+    mut commands: Commands,
+    parents: Query<(&HtmlNodeStyle, &Children), Changed<Children>>,
+    mut plain: PlainChildQuery,
+) {
+    for (parent, children) in &parents {
+        separate_children(
+            &mut commands,
+            &mut plain,
+            &parent.style,
+            &parent.style,
+            children,
+        );
+    }
+}
+
+/// Apply the separation of the style to the plain children that follow another child, and
+/// reset the separation of the previous style of children that don't anymore
+///
+/// Children that are already separated by the same style are left alone, so changes to their
+/// margins by other systems are kept.
+fn separate_children(
+    commands: &mut Commands,
+    plain: &mut PlainChildQuery,
+    previous: &Style,
+    style: &Style,
+    children: &Children,
+) {
+    let same = previous.same_separation(style);
+    for (index, child) in children.iter().enumerate() {
+        let Ok((mut node, mut border_color, separated)) = plain.get_mut(child) else {
+            continue;
+        };
+        let follows = index > 0 && style.separates_children();
+        if same && separated == follows {
+            continue;
+        }
+
+        let node = &mut *node;
+        if separated {
+            previous.reset_separation(&mut node.margin, &mut node.border, &mut border_color);
+        }
+        if follows {
+            style.separate_child(&mut node.margin, &mut node.border, &mut border_color);
+            commands.entity(child).insert(HtmlSeparated);
+        } else if separated {
+            commands.entity(child).remove::<HtmlSeparated>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::testing::context;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<HtmlColorScheme>()
+            .add_systems(Update, update_variant_state);
+        app
+    }
+
+    /// App that restyles nodes like the plugin
+    fn restyle_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<HtmlColorScheme>().add_systems(
+            Update,
+            (
+                update_variant_state,
+                apply_class_changes,
+                restyle_nodes,
+                separate_plain_children,
+            )
+                .chain(),
+        );
+        app
+    }
+
+    /// Spawn a node as if it was spawned from a document
+    fn spawn(app: &mut App, classes: &str, parent: Option<Entity>) -> Entity {
+        let spawn = SpawnContext {
+            context: Arc::new(context()),
+            state: VariantState::default(),
+        };
+        let world = app.world_mut();
+        let mut commands = world.commands();
+        let mut entity = commands.spawn(Interaction::None);
+        if let Some(parent) = parent {
+            entity.insert(ChildOf(parent));
+        }
+        let style = Style::parse(classes, "", &spawn.context, &spawn.state, None).unwrap();
+        HtmlNodeStyle::new(&style, &spawn).spawn(&mut entity, classes);
+        let entity = entity.id();
+        world.flush();
+        entity
+    }
+
+    fn state(app: &App, entity: Entity) -> &VariantState {
+        &app.world().get::<HtmlNodeStyle>(entity).unwrap().state
+    }
+
+    #[test]
+    fn only_affected_nodes_are_updated() {
+        let mut app = app();
+        let group = spawn(&mut app, "group", None);
+        let peer = spawn(&mut app, "peer", Some(group));
+        let follower = spawn(&mut app, "", Some(group));
+        let other = spawn(&mut app, "", None);
+        app.update();
+        assert_eq!(
+            state(&app, follower).position,
+            SiblingPosition { index: 1, count: 2 }
+        );
+
+        // Without change detection, the node is only updated if it is visited anyway
+        *app.world_mut()
+            .get_mut::<Interaction>(other)
+            .unwrap()
+            .bypass_change_detection() = Interaction::Hovered;
+
+        *app.world_mut().get_mut::<Interaction>(group).unwrap() = Interaction::Hovered;
+        app.update();
+        assert!(state(&app, group).element.hovered);
+        assert!(state(&app, peer).group.hovered);
+        assert!(state(&app, follower).group.hovered);
+        assert!(!state(&app, other).element.hovered);
+
+        *app.world_mut().get_mut::<Interaction>(peer).unwrap() = Interaction::Pressed;
+        app.update();
+        assert!(state(&app, follower).peer.pressed);
+        assert!(!state(&app, other).element.hovered);
+
+        app.world_mut().entity_mut(peer).despawn();
+        app.update();
+        assert_eq!(
+            state(&app, follower).position,
+            SiblingPosition { index: 0, count: 1 }
+        );
+        assert_eq!(state(&app, follower).peer, ElementState::default());
+        assert!(!state(&app, other).element.hovered);
+
+        // The color scheme changes for all nodes, but without visiting their relatives
+        app.insert_resource(HtmlColorScheme::Dark);
+        app.update();
+        assert_eq!(state(&app, other).color_scheme, HtmlColorScheme::Dark);
+        assert!(!state(&app, other).element.hovered);
+    }
+
+    #[test]
+    fn focus_changes_update_the_old_and_new_node() {
+        let mut app = app();
+        let first = spawn(&mut app, "", None);
+        let second = spawn(&mut app, "", None);
+        app.insert_resource(InputFocus(Some(first)));
+        app.update();
+        assert!(state(&app, first).element.focused);
+
+        app.insert_resource(InputFocus(Some(second)));
+        app.update();
+        assert!(!state(&app, first).element.focused);
+        assert!(state(&app, second).element.focused);
+    }
+
+    #[test]
+    fn color_scheme_changes_only_dirty_nodes_with_scheme_variants() {
+        let mut app = app();
+        let plain = spawn(&mut app, "p-2 hover:bg-white", None);
+        let dark = spawn(&mut app, "dark:bg-black", None);
+        app.update();
+        // Nodes are restyled by another system
+        for mut node in app
+            .world_mut()
+            .query::<&mut HtmlNodeStyle>()
+            .iter_mut(app.world_mut())
+        {
+            node.dirty = false;
+        }
+
+        app.insert_resource(HtmlColorScheme::Dark);
+        app.update();
+        let node = |entity| app.world().get::<HtmlNodeStyle>(entity).unwrap();
+        assert_eq!(node(plain).state.color_scheme, HtmlColorScheme::Dark);
+        assert!(!node(plain).dirty);
+        assert!(node(dark).dirty);
+    }
+
+    #[test]
+    fn plain_children_are_separated() {
+        let mut app = restyle_app();
+        let list = spawn(&mut app, "space-y-2", None);
+        let rows = [0, 1, 2].map(|_| {
+            app.world_mut()
+                .spawn((Node::default(), BorderColor::default(), ChildOf(list)))
+                .id()
+        });
+        app.update();
+        let margin = |app: &App, row| app.world().get::<Node>(row).unwrap().margin.top;
+        assert_eq!(margin(&app, rows[0]), px(0));
+        assert_eq!(margin(&app, rows[1]), px(8));
+        assert_eq!(margin(&app, rows[2]), px(8));
+
+        // Restyling the list with the same separation keeps changes to the rows
+        app.world_mut().get_mut::<Node>(rows[2]).unwrap().margin.top = px(20);
+        app.world_mut()
+            .get_mut::<HtmlNodeStyle>(list)
+            .unwrap()
+            .dirty = true;
+        app.update();
+        assert_eq!(margin(&app, rows[2]), px(20));
+
+        // The new first row doesn't follow another row anymore
+        app.world_mut().entity_mut(rows[0]).despawn();
+        app.update();
+        assert_eq!(margin(&app, rows[1]), px(0));
+        assert_eq!(margin(&app, rows[2]), px(20));
+
+        app.world_mut()
+            .get_mut::<HtmlClasses>(list)
+            .unwrap()
+            .set("space-y-4");
+        app.update();
+        assert_eq!(margin(&app, rows[1]), px(0));
+        assert_eq!(margin(&app, rows[2]), px(16));
+
+        app.world_mut()
+            .get_mut::<HtmlClasses>(list)
+            .unwrap()
+            .set("flex");
+        app.update();
+        assert_eq!(margin(&app, rows[2]), px(0));
+        assert!(!app.world().entity(rows[2]).contains::<HtmlSeparated>());
+    }
+
+    #[test]
+    fn restyles_only_overwrite_changed_components() {
+        let mut app = restyle_app();
+        let node = spawn(&mut app, "bg-white hover:bg-black text-sm", None);
+        app.update();
+
+        // Like a custom element setting components of its own
+        let font = TextFont::from_font_size(30.0);
+        app.world_mut()
+            .entity_mut(node)
+            .insert((BackgroundColor(Color::WHITE.with_alpha(0.5)), font.clone()));
+
+        *app.world_mut().get_mut::<Interaction>(node).unwrap() = Interaction::Hovered;
+        app.update();
+        let entity = app.world().entity(node);
+        assert_eq!(entity.get::<BackgroundColor>().unwrap().0, Color::BLACK);
+        assert_eq!(entity.get::<TextFont>().unwrap().font_size, font.font_size);
+    }
+}
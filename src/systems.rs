@@ -1,28 +1,51 @@
 use bevy::prelude::*;
+use bevy::ui::ComputedUiRenderTargetInfo;
+use bevy::window::PrimaryWindow;
 use log::info;
 
 use crate::assets::HtmlTailwind;
+use crate::bundle::HtmlAttributes;
+use crate::bundle::HtmlCustomElement;
 use crate::bundle::HtmlId;
 use crate::bundle::HtmlTailwindHandle;
 use crate::bundle::HtmlTailwindSpawned;
+use crate::registry::HtmlElement;
 use crate::registry::HtmlTailwindRegistry;
+use crate::style::HtmlNodeStyle;
+use crate::style::spawn_state;
+use crate::theme::HtmlColorScheme;
 
 /// Spawn the node tree on the entity
 ///
-/// Marks the entity as finished with the HtmlUiSpawned component
+/// Marks the entity as finished with the HtmlUiSpawned component. The nodes start with the
+/// viewport of the entity and the current [`HtmlColorScheme`].
 pub fn spawn_ui(
     mut commands: Commands,
     assets: Res<Assets<HtmlTailwind>>,
-    ui: Query<(Entity, &HtmlTailwindHandle), Without<HtmlTailwindSpawned>>,
+    color_scheme: Res<HtmlColorScheme>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    ui: Query<
+        (
+            Entity,
+            &HtmlTailwindHandle,
+            Option<&ComputedUiRenderTargetInfo>,
+        ),
+        Without<HtmlTailwindSpawned>,
+    >,
 ) {
-    for (entity, handle) in ui {
+    let window = window.single().map(Window::size).unwrap_or_default();
+
+    for (entity, handle, target) in ui {
         let Some(asset) = assets.get(handle.handle.id()) else {
             continue;
         };
 
         if let Ok(mut entity_commands) = commands.get_entity(entity) {
             entity_commands.insert(HtmlTailwindSpawned);
-            asset.dom.apply_to_entity(&mut entity_commands);
+            asset.apply_to_entity(
+                &mut entity_commands,
+                spawn_state(target, window, &color_scheme),
+            );
         }
     }
 }
@@ -42,16 +65,79 @@ pub fn apply_markers(
     }
 }
 
+/// Applies the handlers of registered attributes to newly created HTML nodes
+pub fn apply_attributes(
+    mut commands: Commands,
+    registry: Option<Res<HtmlTailwindRegistry>>,
+    new_nodes: Query<(Entity, &HtmlAttributes), Added<HtmlAttributes>>,
+) {
+    let Some(registry) = registry else { return };
+
+    for (entity, attributes) in &new_nodes {
+        if let Ok(mut entity_cmd) = commands.get_entity(entity) {
+            for (name, value) in attributes.iter() {
+                registry.apply_attribute(name, value, &mut entity_cmd);
+            }
+        }
+    }
+}
+
+/// Newly created custom elements
+type CustomElementQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static HtmlCustomElement,
+        &'static HtmlNodeStyle,
+        Option<&'static HtmlAttributes>,
+    ),
+    Added<HtmlCustomElement>,
+>;
+
+/// Builds newly created custom elements with the functions registered for their tags
+pub fn build_custom_elements(
+    mut commands: Commands,
+    registry: Option<Res<HtmlTailwindRegistry>>,
+    new_elements: CustomElementQuery,
+) {
+    let Some(registry) = registry else { return };
+
+    for (entity, element, node, attributes) in &new_elements {
+        if let Ok(mut entity_cmd) = commands.get_entity(entity) {
+            let element = HtmlElement {
+                tag: &element.tag,
+                attributes: attributes
+                    .map(|attributes| &attributes.0[..])
+                    .unwrap_or_default(),
+                style: &node.style,
+            };
+            registry.build_element(&element, &mut entity_cmd);
+        }
+    }
+}
+
 /// Hot-reloading
 pub fn sync_system(
     mut commands: Commands,
     mut events: MessageReader<AssetEvent<HtmlTailwind>>,
     assets: Res<Assets<HtmlTailwind>>,
-    ui: Query<(Entity, &HtmlTailwindHandle), With<HtmlTailwindSpawned>>,
+    color_scheme: Res<HtmlColorScheme>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    ui: Query<
+        (
+            Entity,
+            &HtmlTailwindHandle,
+            Option<&ComputedUiRenderTargetInfo>,
+        ),
+        With<HtmlTailwindSpawned>,
+    >,
 ) {
+    let window = window.single().map(Window::size).unwrap_or_default();
+
     for event in events.read() {
         if let AssetEvent::Modified { id } = event {
-            for (entity, handle) in ui.iter() {
+            for (entity, handle, target) in ui.iter() {
                 if handle.handle.id() == *id {
                     let Some(asset) = assets.get(*id) else {
                         continue;
@@ -60,7 +146,10 @@ pub fn sync_system(
                     if let Ok(mut entity_cmd) = commands.get_entity(entity) {
                         entity_cmd.despawn_children();
 
-                        asset.dom.apply_to_entity(&mut entity_cmd);
+                        asset.apply_to_entity(
+                            &mut entity_cmd,
+                            spawn_state(target, window, &color_scheme),
+                        );
 
                         info!("UI hot-reloaded for entity {:?}", entity);
                     }
@@ -69,3 +158,96 @@ pub fn sync_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::registry::HtmlTailwindAppExt;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Tooltip(String);
+
+    /// App that applies the handlers, recording every call of the `data-log` handlers
+    fn app() -> (App, Arc<Mutex<Vec<String>>>) {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let mut app = App::new();
+        app.init_resource::<HtmlTailwindRegistry>()
+            .add_systems(Update, apply_attributes)
+            .register_html_attribute("data-tooltip", |value, commands| {
+                commands.insert(Tooltip(value.to_string()));
+            });
+        for handler in ["first", "second"] {
+            let calls = calls.clone();
+            app.register_html_attribute("data-log", move |value, _| {
+                calls.lock().unwrap().push(format!("{handler}: {value}"));
+            });
+        }
+        (app, calls)
+    }
+
+    fn attributes(attributes: &[(&str, &str)]) -> HtmlAttributes {
+        HtmlAttributes(
+            attributes
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn handlers_receive_the_value_of_their_attribute() {
+        let (mut app, calls) = app();
+        let potion = app
+            .world_mut()
+            .spawn(attributes(&[
+                ("class", "p-2"),
+                ("data-tooltip", "Heals 10 HP"),
+            ]))
+            .id();
+        let plain = app.world_mut().spawn(attributes(&[("class", "p-2")])).id();
+        app.update();
+
+        assert_eq!(
+            app.world().get::<Tooltip>(potion),
+            Some(&Tooltip("Heals 10 HP".to_string()))
+        );
+        assert_eq!(app.world().get::<Tooltip>(plain), None);
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn all_handlers_of_an_attribute_are_called_in_order() {
+        let (mut app, calls) = app();
+        app.world_mut().spawn(attributes(&[("data-log", "a")]));
+        app.update();
+        assert_eq!(*calls.lock().unwrap(), ["first: a", "second: a"]);
+    }
+
+    #[test]
+    fn handlers_are_applied_once_per_spawned_node() {
+        let (mut app, calls) = app();
+        let node = app.world_mut().spawn(attributes(&[("data-log", "a")])).id();
+        app.update();
+        app.update();
+        assert_eq!(calls.lock().unwrap().len(), 2);
+
+        // Hot-reloading replaces the nodes by new entities
+        app.world_mut().entity_mut(node).despawn();
+        let node = app
+            .world_mut()
+            .spawn(attributes(&[("data-log", "b"), ("data-tooltip", "c")]))
+            .id();
+        app.update();
+        assert_eq!(
+            *calls.lock().unwrap(),
+            ["first: a", "second: a", "first: b", "second: b"]
+        );
+        assert_eq!(
+            app.world().get::<Tooltip>(node),
+            Some(&Tooltip("c".to_string()))
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Design tokens that are shared by all documents
+
+use std::sync::Arc;
+
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Design tokens that extend or override the defaults of Tailwind
+///
+/// The theme is set by [`HtmlTailwindPlugin::theme`](crate::HtmlTailwindPlugin::theme) and can
+/// be extended by a RON file in the assets directory, see
+/// [`HtmlTailwindPlugin::theme_file`](crate::HtmlTailwindPlugin::theme_file):
+///
+/// ```ron
+/// (
+///     colors: { "brand": "#ff8800", "surface": "rgb(20 20 28)" },
+///     spacing: { "gutter": "24px" },
+///     breakpoints: { "deck": 1280.0 },
+///     radius: { "card": "0.75rem" },
+///     font_sizes: { "huge": (size: 80.0, line_height: Some(1.0)) },
+///     color_schemes: ["high-contrast"],
+/// )
+/// ```
+///
+/// Every entry of a file is added to the theme, replacing an entry with the same name.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HtmlTheme {
+    /// Colors by name as CSS color values, e.g. `bg-brand`
+    #[serde(default)]
+    pub colors: HashMap<String, String>,
+    /// Lengths by name, e.g. `p-gutter`
+    ///
+    /// Steps of the spacing scale like `4` can be overridden as well.
+    #[serde(default)]
+    pub spacing: HashMap<String, String>,
+    /// Minimum width of the viewport in pixels for a breakpoint, e.g. `md`
+    #[serde(default)]
+    pub breakpoints: HashMap<String, f32>,
+    /// Border radii by name, e.g. `rounded-lg`
+    #[serde(default)]
+    pub radius: HashMap<String, String>,
+    /// Font sizes by name, e.g. `text-xl`
+    #[serde(default)]
+    pub font_sizes: HashMap<String, ThemeFontSize>,
+    /// Names of custom color schemes besides light and dark, e.g. `high-contrast`
+    ///
+    /// Each of them is a variant that applies while it is the [`HtmlColorScheme`].
+    #[serde(default)]
+    pub color_schemes: Vec<String>,
+}
+
+/// Font size of a theme
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct ThemeFontSize {
+    /// Size in pixels
+    pub size: f32,
+    /// Line height relative to the font size
+    #[serde(default)]
+    pub line_height: Option<f32>,
+}
+
+impl HtmlTheme {
+    /// Add all entries of the other theme, replacing existing entries with the same name
+    pub fn extend(&mut self, other: HtmlTheme) {
+        self.colors.extend(other.colors);
+        self.spacing.extend(other.spacing);
+        self.breakpoints.extend(other.breakpoints);
+        self.radius.extend(other.radius);
+        self.font_sizes.extend(other.font_sizes);
+        for scheme in other.color_schemes {
+            if !self.color_schemes.contains(&scheme) {
+                self.color_schemes.push(scheme);
+            }
+        }
+    }
+}
+
+impl Default for HtmlTheme {
+    /// The defaults of Tailwind
+    fn default() -> Self {
+        let font_size = |size: f32, line_height: f32| ThemeFontSize {
+            size,
+            line_height: Some(line_height),
+        };
+
+        Self {
+            colors: HashMap::new(),
+            spacing: HashMap::new(),
+            breakpoints: [
+                ("sm", 640.0),
+                ("md", 768.0),
+                ("lg", 1024.0),
+                ("xl", 1280.0),
+                ("2xl", 1536.0),
+            ]
+            .into_iter()
+            .map(|(name, width)| (name.to_string(), width))
+            .collect(),
+            radius: [
+                ("xs", "2px"),
+                ("sm", "4px"),
+                ("md", "6px"),
+                ("lg", "8px"),
+                ("xl", "12px"),
+                ("2xl", "16px"),
+                ("3xl", "24px"),
+                ("4xl", "32px"),
+            ]
+            .into_iter()
+            .map(|(name, radius)| (name.to_string(), radius.to_string()))
+            .collect(),
+            font_sizes: [
+                ("xs", font_size(12.0, 1.0 / 0.75)),
+                ("sm", font_size(14.0, 1.25 / 0.875)),
+                ("base", font_size(16.0, 1.5 / 1.0)),
+                ("lg", font_size(18.0, 1.75 / 1.125)),
+                ("xl", font_size(20.0, 1.75 / 1.25)),
+                ("2xl", font_size(24.0, 2.0 / 1.5)),
+                ("3xl", font_size(30.0, 2.25 / 1.875)),
+                ("4xl", font_size(36.0, 2.5 / 2.25)),
+                ("5xl", font_size(48.0, 1.0)),
+                ("6xl", font_size(60.0, 1.0)),
+                ("7xl", font_size(72.0, 1.0)),
+                ("8xl", font_size(96.0, 1.0)),
+                ("9xl", font_size(128.0, 1.0)),
+            ]
+            .into_iter()
+            .map(|(name, size)| (name.to_string(), size))
+            .collect(),
+            color_schemes: vec![],
+        }
+    }
+}
+
+/// Color scheme of all documents, which selects variants like `dark:`
+///
+/// Nodes with color scheme variants are restyled when the resource changes.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub enum HtmlColorScheme {
+    /// Default scheme without a variant
+    #[default]
+    Light,
+    /// Applies the `dark:` variants
+    Dark,
+    /// Applies the variants of a scheme listed in [`HtmlTheme::color_schemes`]
+    Custom(Arc<str>),
+}
+
+impl HtmlColorScheme {
+    /// Custom scheme with the given name, e.g. `high-contrast`
+    pub fn custom(name: impl Into<Arc<str>>) -> Self {
+        Self::Custom(name.into())
+    }
+
+    /// Whether the scheme is active for a variant
+    ///
+    /// `None` if the variant is not a color scheme of the theme.
+    pub(crate) fn matches(&self, variant: &str, theme: &HtmlTheme) -> Option<bool> {
+        match self {
+            _ if variant == "dark" => Some(*self == Self::Dark),
+            _ if !theme.color_schemes.iter().any(|scheme| scheme == variant) => None,
+            Self::Custom(name) => Some(name.as_ref() == variant),
+            Self::Light | Self::Dark => Some(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries<T: Clone + Into<V>, V>(entries: &[(&str, T)]) -> HashMap<String, V> {
+        entries
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone().into()))
+            .collect()
+    }
+
+    #[test]
+    fn extend_adds_and_overrides_entries() {
+        let mut theme = HtmlTheme {
+            colors: entries(&[("brand", "#ff8800"), ("surface", "#222")]),
+            spacing: entries(&[("gutter", "24px")]),
+            ..Default::default()
+        };
+        theme.extend(HtmlTheme {
+            colors: entries(&[("brand", "#0088ff"), ("accent", "red")]),
+            spacing: HashMap::new(),
+            breakpoints: entries(&[("md", 900.0), ("deck", 1280.0)]),
+            radius: entries(&[("card", "0.75rem")]),
+            font_sizes: entries(&[(
+                "xl",
+                ThemeFontSize {
+                    size: 22.0,
+                    line_height: None,
+                },
+            )]),
+            color_schemes: vec![],
+        });
+
+        assert_eq!(
+            theme.colors,
+            entries(&[("brand", "#0088ff"), ("surface", "#222"), ("accent", "red"),])
+        );
+        assert_eq!(theme.spacing, entries(&[("gutter", "24px")]));
+        assert_eq!(theme.breakpoints["md"], 900.0);
+        assert_eq!(theme.breakpoints["deck"], 1280.0);
+        assert_eq!(theme.breakpoints["lg"], 1024.0);
+        assert_eq!(theme.radius["card"], "0.75rem");
+        assert_eq!(theme.radius["lg"], "8px");
+        assert_eq!(theme.font_sizes["xl"].size, 22.0);
+        assert_eq!(theme.font_sizes["xl"].line_height, None);
+        assert_eq!(theme.font_sizes["lg"].size, 18.0);
+    }
+
+    #[test]
+    fn extend_keeps_color_schemes_unique() {
+        let mut theme = HtmlTheme {
+            color_schemes: vec!["sepia".to_string(), "high-contrast".to_string()],
+            ..Default::default()
+        };
+        theme.extend(HtmlTheme {
+            color_schemes: vec!["high-contrast".to_string(), "night".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(theme.color_schemes, ["sepia", "high-contrast", "night"]);
+    }
+
+    #[test]
+    fn theme_files_may_omit_entries() {
+        let file = ron::from_str::<HtmlTheme>(
+            r##"(colors: { "brand": "#ff8800" }, font_sizes: { "huge": (size: 80.0) })"##,
+        )
+        .unwrap();
+        assert_eq!(file.colors["brand"], "#ff8800");
+        assert_eq!(file.font_sizes["huge"].line_height, None);
+        assert!(file.spacing.is_empty());
+        assert!(file.breakpoints.is_empty());
+        assert!(file.color_schemes.is_empty());
+
+        assert!(ron::from_str::<HtmlTheme>(r##"(colors: { "brand": 5 })"##).is_err());
+        assert!(ron::from_str::<HtmlTheme>("(font_sizes: { \"huge\": () })").is_err());
+    }
+
+    #[test]
+    fn color_scheme_variants() {
+        let theme = HtmlTheme {
+            color_schemes: vec!["sepia".to_string()],
+            ..Default::default()
+        };
+        let sepia = HtmlColorScheme::custom("sepia");
+
+        assert_eq!(HtmlColorScheme::Light.matches("dark", &theme), Some(false));
+        assert_eq!(HtmlColorScheme::Dark.matches("dark", &theme), Some(true));
+        assert_eq!(sepia.matches("dark", &theme), Some(false));
+
+        assert_eq!(HtmlColorScheme::Light.matches("sepia", &theme), Some(false));
+        assert_eq!(HtmlColorScheme::Dark.matches("sepia", &theme), Some(false));
+        assert_eq!(sepia.matches("sepia", &theme), Some(true));
+
+        // Only the schemes of the theme are variants
+        assert_eq!(HtmlColorScheme::Light.matches("light", &theme), None);
+        assert_eq!(sepia.matches("night", &theme), None);
+        assert_eq!(
+            HtmlColorScheme::custom("night").matches("night", &theme),
+            None
+        );
+        assert_eq!(sepia.matches("hover", &theme), None);
+    }
+}